@@ -0,0 +1,190 @@
+//! Addressing an unbounded grid that is split into equally shaped chunks.
+//!
+//! ```
+//! use ndshape::{ChunkedLayout, ConstPow2Shape3i32};
+//!
+//! // Chunks of 16 x 16 x 16 voxels.
+//! let layout = ChunkedLayout::new(ConstPow2Shape3i32::<4, 4, 4>);
+//!
+//! let (key, index) = layout.split([-1, 17, 35]);
+//! assert_eq!(key, [-1, 1, 2]);
+//! assert_eq!(index, 15 + (1 << 4) + (3 << 8));
+//! assert_eq!(layout.join(key, index), [-1, 17, 35]);
+//!
+//! assert_eq!(layout.chunk_extent([-1, 1, 2]), ([-16, 16, 32], [16, 16, 16]));
+//!
+//! let keys: Vec<_> = layout.chunk_keys_overlapping([-1, 0, 0], [2, 16, 17]).collect();
+//! assert_eq!(keys, [[-1, 0, 0], [0, 0, 0], [-1, 0, 1], [0, 0, 1]]);
+//! ```
+
+use crate::{
+    ConstPow2Shape2i128, ConstPow2Shape2i16, ConstPow2Shape2i32, ConstPow2Shape2i64,
+    ConstPow2Shape2i8, ConstPow2Shape2u128, ConstPow2Shape2u16, ConstPow2Shape2u32,
+    ConstPow2Shape2u64, ConstPow2Shape2u8, ConstPow2Shape2usize, ConstPow2Shape3i128,
+    ConstPow2Shape3i16, ConstPow2Shape3i32, ConstPow2Shape3i64, ConstPow2Shape3i8,
+    ConstPow2Shape3u128, ConstPow2Shape3u16, ConstPow2Shape3u32, ConstPow2Shape3u64,
+    ConstPow2Shape3u8, ConstPow2Shape3usize, ConstPow2Shape4i128, ConstPow2Shape4i16,
+    ConstPow2Shape4i32, ConstPow2Shape4i64, ConstPow2Shape4i8, ConstPow2Shape4u128,
+    ConstPow2Shape4u16, ConstPow2Shape4u32, ConstPow2Shape4u64, ConstPow2Shape4u8,
+    ConstPow2Shape4usize, ConstShape, ShapeScalar,
+};
+
+/// Splits global coordinates into a chunk key and a linear index within the chunk, where every chunk has the
+/// power-of-two shape `S`.
+///
+/// Chunk keys are the global coordinates divided by the chunk shape, rounding towards negative infinity. All of the
+/// conversions are bit shifts and masks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChunkedLayout<S> {
+    chunk_shape: S,
+}
+
+impl<S> ChunkedLayout<S> {
+    pub fn new(chunk_shape: S) -> Self {
+        Self { chunk_shape }
+    }
+
+    pub fn chunk_shape(&self) -> &S {
+        &self.chunk_shape
+    }
+}
+
+/// An iterator over the keys of all chunks that overlap a box, in row-major order.
+///
+/// Returned by `ChunkedLayout::chunk_keys_overlapping`.
+#[derive(Clone, Debug)]
+pub struct ChunkKeys<C, const N: usize> {
+    min: [C; N],
+    max: [C; N],
+    next: Option<[C; N]>,
+}
+
+impl<C: ShapeScalar, const N: usize> Iterator for ChunkKeys<C, N> {
+    type Item = [C; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<[C; N]> {
+        let key = self.next?;
+        let mut next = key;
+        self.next = None;
+        for i in 0..N {
+            if next[i] < self.max[i] {
+                next[i] = next[i] + C::ONE;
+                self.next = Some(next);
+                break;
+            }
+            next[i] = self.min[i];
+        }
+        Some(key)
+    }
+}
+
+macro_rules! impl_chunked_layout {
+    ($shape:ident, $scalar:ty, $n:literal, $($bits:ident),+) => {
+        impl<$(const $bits: $scalar),+> ChunkedLayout<$shape<$($bits),+>> {
+            const BITS: [$scalar; $n] = [$($bits),+];
+
+            /// The key of the chunk containing `p`.
+            #[inline]
+            pub fn chunk_key(&self, p: [$scalar; $n]) -> [$scalar; $n] {
+                let mut key = p;
+                for i in 0..$n {
+                    key[i] >>= Self::BITS[i];
+                }
+                key
+            }
+
+            /// The linear index of `p` within its chunk.
+            #[inline]
+            pub fn local_index(&self, p: [$scalar; $n]) -> $scalar {
+                let mut index = 0;
+                for i in 0..$n {
+                    index |= (p[i] << $shape::<$($bits),+>::SHIFTS[i]) & $shape::<$($bits),+>::MASKS[i];
+                }
+                index
+            }
+
+            /// The chunk key and local linear index of `p`.
+            #[inline]
+            pub fn split(&self, p: [$scalar; $n]) -> ([$scalar; $n], $scalar) {
+                (self.chunk_key(p), self.local_index(p))
+            }
+
+            /// The inverse of `split`.
+            #[inline]
+            pub fn join(&self, key: [$scalar; $n], index: $scalar) -> [$scalar; $n] {
+                let mut p = key;
+                for i in 0..$n {
+                    p[i] = (key[i] << Self::BITS[i])
+                        | ((index & $shape::<$($bits),+>::MASKS[i]) >> $shape::<$($bits),+>::SHIFTS[i]);
+                }
+                p
+            }
+
+            /// The minimum global coordinates and shape of the chunk at `key`.
+            #[inline]
+            pub fn chunk_extent(&self, key: [$scalar; $n]) -> ([$scalar; $n], [$scalar; $n]) {
+                let mut min = key;
+                for i in 0..$n {
+                    min[i] <<= Self::BITS[i];
+                }
+                (min, $shape::<$($bits),+>::ARRAY)
+            }
+
+            /// Iterates over the keys of all chunks that overlap the box starting at `min` with shape `box_shape`.
+            pub fn chunk_keys_overlapping(
+                &self,
+                min: [$scalar; $n],
+                box_shape: [$scalar; $n],
+            ) -> ChunkKeys<$scalar, $n> {
+                let mut max = min;
+                for i in 0..$n {
+                    max[i] += box_shape[i].max(1) - 1;
+                }
+                let min = self.chunk_key(min);
+                let max = self.chunk_key(max);
+                let is_empty = box_shape.iter().any(|&d| d < 1);
+                ChunkKeys { min, max, next: (!is_empty).then_some(min) }
+            }
+        }
+    };
+}
+
+impl_chunked_layout!(ConstPow2Shape2u8, u8, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2u16, u16, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2u32, u32, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2u64, u64, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2u128, u128, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2usize, usize, 2, X, Y);
+
+impl_chunked_layout!(ConstPow2Shape2i8, i8, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2i16, i16, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2i32, i32, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2i64, i64, 2, X, Y);
+impl_chunked_layout!(ConstPow2Shape2i128, i128, 2, X, Y);
+
+impl_chunked_layout!(ConstPow2Shape3u8, u8, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3u16, u16, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3u32, u32, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3u64, u64, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3u128, u128, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3usize, usize, 3, X, Y, Z);
+
+impl_chunked_layout!(ConstPow2Shape3i8, i8, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3i16, i16, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3i32, i32, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3i64, i64, 3, X, Y, Z);
+impl_chunked_layout!(ConstPow2Shape3i128, i128, 3, X, Y, Z);
+
+impl_chunked_layout!(ConstPow2Shape4u8, u8, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4u16, u16, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4u32, u32, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4u64, u64, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4u128, u128, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4usize, usize, 4, X, Y, Z, W);
+
+impl_chunked_layout!(ConstPow2Shape4i8, i8, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4i16, i16, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4i32, i32, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4i64, i64, 4, X, Y, Z, W);
+impl_chunked_layout!(ConstPow2Shape4i128, i128, 4, X, Y, Z, W);
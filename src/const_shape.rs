@@ -1,10 +1,10 @@
-use crate::{AbstractShape, ConstShape, Shape};
+use crate::{lod, AbstractShape, ConstShape, LodShape, RuntimePow2Shape, RuntimeShape, Shape};
 
 use static_assertions::assert_impl_all;
 
 macro_rules! impl_const_shape2 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar> $name<X, Y> {
@@ -15,6 +15,7 @@ macro_rules! impl_const_shape2 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 2] = [X, Y];
+            const STRIDES: [$scalar; 2] = $name::<X, Y>::STRIDES;
             const SIZE: $scalar = X * Y;
             const USIZE: usize = Self::SIZE as usize;
 
@@ -25,14 +26,27 @@ macro_rules! impl_const_shape2 {
 
             #[inline]
             fn delinearize(i: $scalar) -> [$scalar; 2] {
+                if Self::SIZE == 0 {
+                    return [0; 2];
+                }
                 let y = i / Self::STRIDES[1];
                 let x = i % Self::STRIDES[1];
                 [x, y]
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar> LodShape<2> for $name<X, Y> {
+            type Level = RuntimeShape<$scalar, 2>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimeShape::<$scalar, 2>::new(lod::downsample_dims(Self::ARRAY, level))
+            }
+        }
+
         assert_impl_all!($name<1, 1>: AbstractShape<$scalar, [$scalar; 2]>);
         assert_impl_all!($name<1, 1>: Shape<2>);
+        assert_impl_all!($name<1, 1>: LodShape<2>);
     };
 }
 
@@ -40,16 +54,18 @@ impl_const_shape2!(ConstShape2u8, u8);
 impl_const_shape2!(ConstShape2u16, u16);
 impl_const_shape2!(ConstShape2u32, u32);
 impl_const_shape2!(ConstShape2u64, u64);
+impl_const_shape2!(ConstShape2u128, u128);
 impl_const_shape2!(ConstShape2usize, usize);
 
 impl_const_shape2!(ConstShape2i8, i8);
 impl_const_shape2!(ConstShape2i16, i16);
 impl_const_shape2!(ConstShape2i32, i32);
 impl_const_shape2!(ConstShape2i64, i64);
+impl_const_shape2!(ConstShape2i128, i128);
 
 macro_rules! impl_const_shape3 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar, const Z: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar, const Z: $scalar> $name<X, Y, Z> {
@@ -62,6 +78,7 @@ macro_rules! impl_const_shape3 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 3] = [X, Y, Z];
+            const STRIDES: [$scalar; 3] = $name::<X, Y, Z>::STRIDES;
             const SIZE: $scalar = X * Y * Z;
             const USIZE: usize = Self::SIZE as usize;
 
@@ -72,6 +89,9 @@ macro_rules! impl_const_shape3 {
 
             #[inline]
             fn delinearize(mut i: $scalar) -> [$scalar; 3] {
+                if Self::SIZE == 0 {
+                    return [0; 3];
+                }
                 let z = i / Self::STRIDES[2];
                 i -= z * Self::STRIDES[2];
                 let y = i / Self::STRIDES[1];
@@ -80,8 +100,18 @@ macro_rules! impl_const_shape3 {
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar, const Z: $scalar> LodShape<3> for $name<X, Y, Z> {
+            type Level = RuntimeShape<$scalar, 3>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimeShape::<$scalar, 3>::new(lod::downsample_dims(Self::ARRAY, level))
+            }
+        }
+
         assert_impl_all!($name<1, 1, 1>: AbstractShape<$scalar, [$scalar; 3]>);
         assert_impl_all!($name<1, 1, 1>: Shape<3>);
+        assert_impl_all!($name<1, 1, 1>: LodShape<3>);
     };
 }
 
@@ -89,16 +119,18 @@ impl_const_shape3!(ConstShape3u8, u8);
 impl_const_shape3!(ConstShape3u16, u16);
 impl_const_shape3!(ConstShape3u32, u32);
 impl_const_shape3!(ConstShape3u64, u64);
+impl_const_shape3!(ConstShape3u128, u128);
 impl_const_shape3!(ConstShape3usize, usize);
 
 impl_const_shape3!(ConstShape3i8, i8);
 impl_const_shape3!(ConstShape3i16, i16);
 impl_const_shape3!(ConstShape3i32, i32);
 impl_const_shape3!(ConstShape3i64, i64);
+impl_const_shape3!(ConstShape3i128, i128);
 
 macro_rules! impl_const_shape4 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar>
@@ -113,6 +145,7 @@ macro_rules! impl_const_shape4 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 4] = [X, Y, Z, W];
+            const STRIDES: [$scalar; 4] = $name::<X, Y, Z, W>::STRIDES;
             const SIZE: $scalar = X * Y * Z * W;
             const USIZE: usize = Self::SIZE as usize;
 
@@ -126,6 +159,9 @@ macro_rules! impl_const_shape4 {
 
             #[inline]
             fn delinearize(mut i: $scalar) -> [$scalar; 4] {
+                if Self::SIZE == 0 {
+                    return [0; 4];
+                }
                 let w = i / Self::STRIDES[3];
                 i -= w * Self::STRIDES[3];
                 let z = i / Self::STRIDES[2];
@@ -136,8 +172,18 @@ macro_rules! impl_const_shape4 {
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar> LodShape<4> for $name<X, Y, Z, W> {
+            type Level = RuntimeShape<$scalar, 4>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimeShape::<$scalar, 4>::new(lod::downsample_dims(Self::ARRAY, level))
+            }
+        }
+
         assert_impl_all!($name<1, 1, 1, 1>: AbstractShape<$scalar, [$scalar; 4]>);
         assert_impl_all!($name<1, 1, 1, 1>: Shape<4>);
+        assert_impl_all!($name<1, 1, 1, 1>: LodShape<4>);
     };
 }
 
@@ -145,19 +191,23 @@ impl_const_shape4!(ConstShape4u8, u8);
 impl_const_shape4!(ConstShape4u16, u16);
 impl_const_shape4!(ConstShape4u32, u32);
 impl_const_shape4!(ConstShape4u64, u64);
+impl_const_shape4!(ConstShape4u128, u128);
 impl_const_shape4!(ConstShape4usize, usize);
 
 impl_const_shape4!(ConstShape4i8, i8);
 impl_const_shape4!(ConstShape4i16, i16);
 impl_const_shape4!(ConstShape4i32, i32);
 impl_const_shape4!(ConstShape4i64, i64);
+impl_const_shape4!(ConstShape4i128, i128);
 
 macro_rules! impl_const_pow2_shape2 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar> $name<X, Y> {
+            pub const STRIDES: [$scalar; 2] = [1, 1 << X];
+
             pub const SHIFTS: [$scalar; 2] = [0, X];
 
             pub const MASKS: [$scalar; 2] = [
@@ -170,6 +220,7 @@ macro_rules! impl_const_pow2_shape2 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 2] = [1 << X, 1 << Y];
+            const STRIDES: [$scalar; 2] = $name::<X, Y>::STRIDES;
             const SIZE: $scalar = 1 << (X + Y);
             const USIZE: usize = Self::SIZE as usize;
 
@@ -184,8 +235,27 @@ macro_rules! impl_const_pow2_shape2 {
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar> LodShape<2> for $name<X, Y> {
+            type Level = RuntimePow2Shape<$scalar, 2>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimePow2Shape::<$scalar, 2>::new(lod::downsample_bits([X, Y], level))
+            }
+
+            #[inline]
+            fn parent_index(&self, level: u32, index: $scalar) -> $scalar {
+                lod::pow2_parent_index([X, Y], level, index)
+            }
+
+            fn child_indices(&self, level: u32, index: $scalar) -> Vec<$scalar> {
+                lod::pow2_child_indices([X, Y], level, index)
+            }
+        }
+
         assert_impl_all!($name<1, 1>: AbstractShape<$scalar, [$scalar; 2]>);
         assert_impl_all!($name<1, 1>: Shape<2>);
+        assert_impl_all!($name<1, 1>: LodShape<2>);
     };
 }
 
@@ -193,19 +263,23 @@ impl_const_pow2_shape2!(ConstPow2Shape2u8, u8);
 impl_const_pow2_shape2!(ConstPow2Shape2u16, u16);
 impl_const_pow2_shape2!(ConstPow2Shape2u32, u32);
 impl_const_pow2_shape2!(ConstPow2Shape2u64, u64);
+impl_const_pow2_shape2!(ConstPow2Shape2u128, u128);
 impl_const_pow2_shape2!(ConstPow2Shape2usize, usize);
 
 impl_const_pow2_shape2!(ConstPow2Shape2i8, i8);
 impl_const_pow2_shape2!(ConstPow2Shape2i16, i16);
 impl_const_pow2_shape2!(ConstPow2Shape2i32, i32);
 impl_const_pow2_shape2!(ConstPow2Shape2i64, i64);
+impl_const_pow2_shape2!(ConstPow2Shape2i128, i128);
 
 macro_rules! impl_const_pow2_shape3 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar, const Z: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar, const Z: $scalar> $name<X, Y, Z> {
+            pub const STRIDES: [$scalar; 3] = [1, 1 << X, 1 << (X + Y)];
+
             pub const SHIFTS: [$scalar; 3] = [0, X, X + Y];
 
             pub const MASKS: [$scalar; 3] = [
@@ -221,6 +295,7 @@ macro_rules! impl_const_pow2_shape3 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 3] = [1 << X, 1 << Y, 1 << Z];
+            const STRIDES: [$scalar; 3] = $name::<X, Y, Z>::STRIDES;
             const SIZE: $scalar = 1 << (X + Y + Z);
             const USIZE: usize = Self::SIZE as usize;
 
@@ -239,8 +314,27 @@ macro_rules! impl_const_pow2_shape3 {
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar, const Z: $scalar> LodShape<3> for $name<X, Y, Z> {
+            type Level = RuntimePow2Shape<$scalar, 3>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimePow2Shape::<$scalar, 3>::new(lod::downsample_bits([X, Y, Z], level))
+            }
+
+            #[inline]
+            fn parent_index(&self, level: u32, index: $scalar) -> $scalar {
+                lod::pow2_parent_index([X, Y, Z], level, index)
+            }
+
+            fn child_indices(&self, level: u32, index: $scalar) -> Vec<$scalar> {
+                lod::pow2_child_indices([X, Y, Z], level, index)
+            }
+        }
+
         assert_impl_all!($name<1, 1, 1>: AbstractShape<$scalar, [$scalar; 3]>);
         assert_impl_all!($name<1, 1, 1>: Shape<3>);
+        assert_impl_all!($name<1, 1, 1>: LodShape<3>);
     };
 }
 
@@ -248,21 +342,25 @@ impl_const_pow2_shape3!(ConstPow2Shape3u8, u8);
 impl_const_pow2_shape3!(ConstPow2Shape3u16, u16);
 impl_const_pow2_shape3!(ConstPow2Shape3u32, u32);
 impl_const_pow2_shape3!(ConstPow2Shape3u64, u64);
+impl_const_pow2_shape3!(ConstPow2Shape3u128, u128);
 impl_const_pow2_shape3!(ConstPow2Shape3usize, usize);
 
 impl_const_pow2_shape3!(ConstPow2Shape3i8, i8);
 impl_const_pow2_shape3!(ConstPow2Shape3i16, i16);
 impl_const_pow2_shape3!(ConstPow2Shape3i32, i32);
 impl_const_pow2_shape3!(ConstPow2Shape3i64, i64);
+impl_const_pow2_shape3!(ConstPow2Shape3i128, i128);
 
 macro_rules! impl_const_pow2_shape4 {
     ($name:ident, $scalar:ty) => {
-        #[derive(Clone, Debug, Copy, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Copy, Eq, PartialEq)]
         pub struct $name<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar>;
 
         impl<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar>
             $name<X, Y, Z, W>
         {
+            pub const STRIDES: [$scalar; 4] = [1, 1 << X, 1 << (X + Y), 1 << (X + Y + Z)];
+
             pub const SHIFTS: [$scalar; 4] = [0, X, X + Y, X + Y + Z];
 
             pub const MASKS: [$scalar; 4] = [
@@ -279,6 +377,7 @@ macro_rules! impl_const_pow2_shape4 {
             type Coord = $scalar;
 
             const ARRAY: [$scalar; 4] = [1 << X, 1 << Y, 1 << Z, 1 << W];
+            const STRIDES: [$scalar; 4] = $name::<X, Y, Z, W>::STRIDES;
             const SIZE: $scalar = 1 << (X + Y + Z + W);
             const USIZE: usize = Self::SIZE as usize;
 
@@ -298,8 +397,27 @@ macro_rules! impl_const_pow2_shape4 {
             }
         }
 
+        impl<const X: $scalar, const Y: $scalar, const Z: $scalar, const W: $scalar> LodShape<4> for $name<X, Y, Z, W> {
+            type Level = RuntimePow2Shape<$scalar, 4>;
+
+            #[inline]
+            fn downsample(&self, level: u32) -> Self::Level {
+                RuntimePow2Shape::<$scalar, 4>::new(lod::downsample_bits([X, Y, Z, W], level))
+            }
+
+            #[inline]
+            fn parent_index(&self, level: u32, index: $scalar) -> $scalar {
+                lod::pow2_parent_index([X, Y, Z, W], level, index)
+            }
+
+            fn child_indices(&self, level: u32, index: $scalar) -> Vec<$scalar> {
+                lod::pow2_child_indices([X, Y, Z, W], level, index)
+            }
+        }
+
         assert_impl_all!($name<1, 1, 1, 1>: AbstractShape<$scalar, [$scalar; 4]>);
         assert_impl_all!($name<1, 1, 1, 1>: Shape<4>);
+        assert_impl_all!($name<1, 1, 1, 1>: LodShape<4>);
     };
 }
 
@@ -307,9 +425,11 @@ impl_const_pow2_shape4!(ConstPow2Shape4u8, u8);
 impl_const_pow2_shape4!(ConstPow2Shape4u16, u16);
 impl_const_pow2_shape4!(ConstPow2Shape4u32, u32);
 impl_const_pow2_shape4!(ConstPow2Shape4u64, u64);
+impl_const_pow2_shape4!(ConstPow2Shape4u128, u128);
 impl_const_pow2_shape4!(ConstPow2Shape4usize, usize);
 
 impl_const_pow2_shape4!(ConstPow2Shape4i8, i8);
 impl_const_pow2_shape4!(ConstPow2Shape4i16, i16);
 impl_const_pow2_shape4!(ConstPow2Shape4i32, i32);
 impl_const_pow2_shape4!(ConstPow2Shape4i64, i64);
+impl_const_pow2_shape4!(ConstPow2Shape4i128, i128);
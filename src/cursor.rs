@@ -0,0 +1,117 @@
+use crate::{Shape, ShapeScalar};
+
+/// A point in a shape along with its linear index, which is updated incrementally by adding strides as the point moves,
+/// instead of linearizing again.
+///
+/// The cursor may leave the shape, e.g. to probe a neighbor, in which case coordinates and the index wrap like the rest
+/// of the crate's modular arithmetic. The index is only meaningful while [`Cursor::is_in_bounds`] is true.
+///
+/// ```
+/// use ndshape::{ConstPow2Shape3u32, ConstShape3u32, Cursor, Shape};
+///
+/// let shape = ConstShape3u32::<5, 6, 7>;
+/// let mut cursor = Cursor::new(&shape, [1, 2, 3]);
+/// assert_eq!(cursor.index(), 101);
+///
+/// cursor.step(1, 1);
+/// assert_eq!(cursor.coords(), [1, 3, 3]);
+/// assert_eq!(cursor.index(), shape.linearize([1, 3, 3]));
+///
+/// cursor.offset([-1, 2, -3]);
+/// assert_eq!(cursor.index(), shape.linearize([0, 5, 0]));
+///
+/// cursor.step(0, -1);
+/// assert!(!cursor.is_in_bounds());
+/// cursor.step(0, 1);
+/// assert!(cursor.is_in_bounds());
+///
+/// let shape = ConstPow2Shape3u32::<2, 2, 2>;
+/// let mut cursor = Cursor::new(&shape, [0, 0, 0]);
+/// for _ in 0..3 {
+///     cursor.step(2, 1);
+/// }
+/// assert_eq!(cursor.index(), shape.linearize([0, 0, 3]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cursor<'a, S: ?Sized, C, const N: usize> {
+    shape: &'a S,
+    strides: [C; N],
+    p: [C; N],
+    index: C,
+}
+
+impl<'a, S: ?Sized, C, const N: usize> Cursor<'a, S, C, N>
+where
+    S: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    pub fn new(shape: &'a S, p: [C; N]) -> Self {
+        Self {
+            shape,
+            strides: shape.strides(),
+            p,
+            index: shape.linearize(p),
+        }
+    }
+
+    #[inline]
+    pub fn shape(&self) -> &'a S {
+        self.shape
+    }
+
+    #[inline]
+    pub fn coords(&self) -> [C; N] {
+        self.p
+    }
+
+    /// The linear index of [`Self::coords`].
+    #[inline]
+    pub fn index(&self) -> C {
+        self.index
+    }
+
+    /// Moves `delta` steps along `axis`.
+    #[inline]
+    pub fn step(&mut self, axis: usize, delta: isize) {
+        let steps = C::from_usize(delta.unsigned_abs());
+        if delta < 0 {
+            self.p[axis] = self.p[axis].wrapping_sub(steps);
+            self.index = self
+                .index
+                .wrapping_sub(self.strides[axis].wrapping_mul(steps));
+        } else {
+            self.p[axis] = self.p[axis].wrapping_add(steps);
+            self.index = self
+                .index
+                .wrapping_add(self.strides[axis].wrapping_mul(steps));
+        }
+        self.debug_check();
+    }
+
+    /// Moves by `delta` steps along every axis.
+    #[inline]
+    pub fn offset(&mut self, delta: [isize; N]) {
+        for (axis, d) in delta.into_iter().enumerate() {
+            self.step(axis, d);
+        }
+    }
+
+    /// Whether the cursor is within the shape.
+    #[inline]
+    pub fn is_in_bounds(&self) -> bool {
+        self.p
+            .iter()
+            .zip(self.shape.as_array())
+            .all(|(&c, dim)| C::ZERO <= c && c < dim)
+    }
+
+    #[inline]
+    fn debug_check(&self) {
+        debug_assert!(
+            !self.is_in_bounds() || self.index == self.shape.linearize(self.p),
+            "cursor index {:?} does not match coordinates {:?}",
+            self.index,
+            self.p
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Conversions between linear index ranges and coordinate boxes.
+
+use crate::scalar::{is_negative, magnitude};
+use crate::{BoxIter, Shape, ShapeScalar};
+
+use core::ops::Range;
+
+/// The minimal list of `(min, shape)` boxes whose union is exactly the linear `range` of a row-major layout with
+/// dimensions `array`, in increasing linear order.
+pub(crate) fn linear_range_to_boxes<C: ShapeScalar, const N: usize>(
+    array: [C; N],
+    range: Range<C>,
+) -> Vec<([C; N], [C; N])> {
+    let size = array.iter().fold(C::ONE, |size, &dim| size * dim);
+    assert!(
+        range.start <= range.end && range.end <= size,
+        "linear range {range:?} is out of bounds for size {size:?}"
+    );
+
+    // strides[k] is the number of elements in one step along axis k.
+    let mut strides = [C::ONE; N];
+    for k in 1..N {
+        strides[k] = strides[k - 1] * array[k - 1];
+    }
+    let stride_above = |k: usize| if k + 1 < N { strides[k + 1] } else { size };
+
+    let mut boxes = Vec::new();
+    let mut emit = |cur: C, axis: usize, len: C| {
+        // Axes below `axis` are full, `axis` spans `len` steps, and axes above `axis` are fixed.
+        let mut min = [C::ZERO; N];
+        let mut shape = array;
+        for k in axis..N {
+            min[k] = (cur / strides[k]) % array[k];
+        }
+        shape[axis] = len;
+        for dim in shape.iter_mut().skip(axis + 1) {
+            *dim = C::ONE;
+        }
+        boxes.push((min, shape));
+    };
+
+    let Range { mut start, end } = range;
+    if start == end {
+        return boxes;
+    }
+
+    // Ascend: complete partial rows, then partial slabs, and so on, until the next boundary would overshoot `end`.
+    let mut level = N;
+    for (k, &stride) in strides.iter().enumerate() {
+        let above = stride_above(k);
+        let next = start + (above - start % above) % above;
+        if next > end {
+            level = k + 1;
+            break;
+        }
+        if next > start {
+            emit(start, k, (next - start) / stride);
+            start = next;
+        }
+    }
+
+    // Descend: emit the largest whole blocks that still fit, then smaller ones.
+    for k in (0..level).rev() {
+        let len = (end - start) / strides[k];
+        if len > C::ZERO {
+            emit(start, k, len);
+            start = start + len * strides[k];
+        }
+    }
+
+    boxes
+}
+
+/// The minimal list of `(start, len)` linear index runs covering the box starting at `min` with dimensions `box_shape`,
+/// for a layout with the given `strides`.
+pub(crate) fn box_to_runs<S, C, const N: usize>(
+    shape: &S,
+    strides: [C; N],
+    min: [C; N],
+    box_shape: [C; N],
+) -> Vec<(C, C)>
+where
+    S: Shape<N, Coord = C> + ?Sized,
+    C: ShapeScalar,
+{
+    if box_shape.iter().any(|&d| d <= C::ZERO) {
+        return Vec::new();
+    }
+
+    // Axes of length 1 never break contiguity, so only the others need to be merged, in order of increasing stride.
+    // Zero and negative strides can never extend a run, so they go after all of the positive ones.
+    let mut axes = [0; N];
+    for (i, axis) in axes.iter_mut().enumerate() {
+        *axis = i;
+    }
+    let mut axes = &mut axes[..];
+    axes.sort_by_key(|&axis| {
+        let stride = strides[axis];
+        let is_positive = stride != C::ZERO && !is_negative(stride);
+        (box_shape[axis] == C::ONE, !is_positive, magnitude(stride))
+    });
+    let num_long = axes
+        .iter()
+        .take_while(|&&axis| box_shape[axis] > C::ONE)
+        .count();
+    axes = &mut axes[..num_long];
+
+    // Merge axes as long as each one starts right where the previous ones end.
+    let mut outer_shape = box_shape;
+    let mut run_len = C::ONE;
+    for &axis in axes.iter() {
+        if strides[axis] != run_len {
+            break;
+        }
+        run_len = run_len * box_shape[axis];
+        outer_shape[axis] = C::ONE;
+    }
+
+    BoxIter::new(shape, min, outer_shape)
+        .map(|(start, _)| (start, run_len))
+        .collect()
+}
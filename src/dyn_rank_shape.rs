@@ -0,0 +1,311 @@
+use crate::{AbstractShape, RuntimeShape, Shape, ShapeError, ShapeScalar};
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// The largest rank that a [`DynArray`] stores without allocating.
+pub const INLINE_RANK: usize = 6;
+
+/// A list of coordinates or dimensions whose length is only known at runtime. Up to [`INLINE_RANK`] elements are
+/// stored inline, and longer lists fall back to the heap.
+#[derive(Clone)]
+pub struct DynArray<C> {
+    repr: Repr<C>,
+}
+
+#[derive(Clone)]
+enum Repr<C> {
+    Inline { len: usize, data: [C; INLINE_RANK] },
+    Heap(Vec<C>),
+}
+
+impl<C: ShapeScalar> DynArray<C> {
+    /// `rank` zeros.
+    pub fn zeros(rank: usize) -> Self {
+        let repr = if rank <= INLINE_RANK {
+            Repr::Inline {
+                len: rank,
+                data: [C::ZERO; INLINE_RANK],
+            }
+        } else {
+            Repr::Heap(vec![C::ZERO; rank])
+        };
+        Self { repr }
+    }
+
+    pub fn from_slice(values: &[C]) -> Self {
+        let mut array = Self::zeros(values.len());
+        array.copy_from_slice(values);
+        array
+    }
+
+    /// Whether the elements are stored inline rather than on the heap.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline { .. })
+    }
+}
+
+impl<C> Deref for DynArray<C> {
+    type Target = [C];
+
+    #[inline]
+    fn deref(&self) -> &[C] {
+        match &self.repr {
+            Repr::Inline { len, data } => &data[..*len],
+            Repr::Heap(data) => data,
+        }
+    }
+}
+
+impl<C> DerefMut for DynArray<C> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [C] {
+        match &mut self.repr {
+            Repr::Inline { len, data } => &mut data[..*len],
+            Repr::Heap(data) => data,
+        }
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for DynArray<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<C: PartialEq> PartialEq for DynArray<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl<C: Eq> Eq for DynArray<C> {}
+
+impl<C: PartialEq> PartialEq<[C]> for DynArray<C> {
+    fn eq(&self, other: &[C]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<C: PartialEq, const N: usize> PartialEq<[C; N]> for DynArray<C> {
+    fn eq(&self, other: &[C; N]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<C: ShapeScalar> From<&[C]> for DynArray<C> {
+    fn from(values: &[C]) -> Self {
+        Self::from_slice(values)
+    }
+}
+
+impl<C: ShapeScalar, const N: usize> From<[C; N]> for DynArray<C> {
+    fn from(values: [C; N]) -> Self {
+        Self::from_slice(&values)
+    }
+}
+
+/// The row-major shape of an array whose number of dimensions is only known at runtime.
+///
+/// Once the rank is known, the shape can be converted into a [`RuntimeShape`] for faster indexing.
+///
+/// ```
+/// use ndshape::{DynRankShape, RuntimeShape, Shape};
+///
+/// // E.g. read from a file header.
+/// let dims: Vec<u32> = vec![5, 6, 7];
+///
+/// let shape = DynRankShape::new(&dims);
+/// assert_eq!(shape.rank(), 3);
+/// assert_eq!(shape.size(), 210);
+///
+/// let index = shape.linearize(&[1, 2, 3]);
+/// assert_eq!(index, 101);
+/// assert_eq!(shape.delinearize(index), [1, 2, 3]);
+/// assert!(shape.delinearize(index).is_inline());
+///
+/// let coords: Vec<_> = shape.iter_coords().collect();
+/// assert_eq!(coords.len(), 210);
+/// assert_eq!(coords[101], (101, [1, 2, 3].into()));
+///
+/// let fixed: RuntimeShape<u32, 3> = shape.to_fixed_rank().unwrap();
+/// assert_eq!(fixed.linearize([1, 2, 3]), 101);
+/// assert!(shape.to_fixed_rank::<2>().is_err());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DynRankShape<C> {
+    array: DynArray<C>,
+    strides: DynArray<C>,
+    size: C,
+}
+
+impl<C: ShapeScalar> DynRankShape<C> {
+    pub fn new(array: &[C]) -> Self {
+        let mut strides = DynArray::zeros(array.len());
+        let mut size = C::ONE;
+        for (stride, &dim) in strides.iter_mut().zip(array) {
+            *stride = size;
+            size = size * dim;
+        }
+        Self {
+            array: DynArray::from_slice(array),
+            strides,
+            size,
+        }
+    }
+
+    /// The number of dimensions.
+    #[inline]
+    pub fn rank(&self) -> usize {
+        self.array.len()
+    }
+
+    /// The dimensions of the shape.
+    #[inline]
+    pub fn as_slice(&self) -> &[C] {
+        &self.array
+    }
+
+    #[inline]
+    pub fn strides(&self) -> &[C] {
+        &self.strides
+    }
+
+    /// The number of elements in an array with this shape.
+    #[inline]
+    pub fn size(&self) -> C {
+        self.size
+    }
+
+    /// The same as `self.size() as usize`.
+    #[inline]
+    pub fn usize(&self) -> usize {
+        self.size.to_usize()
+    }
+
+    /// Whether the shape has no elements, i.e. some dimension is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.array.iter().any(|&dim| dim <= C::ZERO)
+    }
+
+    /// Translates the coordinates `p`, which must have [`Self::rank`] elements, into a linear index.
+    #[inline]
+    pub fn linearize(&self, p: &[C]) -> C {
+        assert_eq!(p.len(), self.rank(), "coordinates must match the rank");
+        self.strides
+            .iter()
+            .zip(p)
+            .fold(C::ZERO, |i, (&stride, &c)| {
+                i.wrapping_add(stride.wrapping_mul(c))
+            })
+    }
+
+    /// The inverse of `linearize`, writing the coordinates into `p`, which must have [`Self::rank`] elements.
+    #[inline]
+    pub fn delinearize_into(&self, mut i: C, p: &mut [C]) {
+        assert_eq!(p.len(), self.rank(), "coordinates must match the rank");
+        p.fill(C::ZERO);
+        if self.size == C::ZERO {
+            return;
+        }
+        for k in (1..p.len()).rev() {
+            p[k] = i / self.strides[k];
+            i = i - p[k] * self.strides[k];
+        }
+        if let Some(c) = p.first_mut() {
+            *c = i;
+        }
+    }
+
+    /// The inverse of `linearize`.
+    #[inline]
+    pub fn delinearize(&self, i: C) -> DynArray<C> {
+        let mut p = DynArray::zeros(self.rank());
+        self.delinearize_into(i, &mut p);
+        p
+    }
+
+    /// All points of the shape in linear order, along with their linear indices.
+    pub fn iter_coords(&self) -> DynCoords<'_, C> {
+        DynCoords {
+            shape: self,
+            index: C::ZERO,
+            next: (!self.is_empty()).then(|| DynArray::zeros(self.rank())),
+        }
+    }
+
+    /// The same shape with a rank known at compile time.
+    ///
+    /// Returns [`ShapeError::RankMismatch`] unless `N` equals [`Self::rank`].
+    pub fn to_fixed_rank<const N: usize>(&self) -> Result<RuntimeShape<C, N>, ShapeError> {
+        let array = <[C; N]>::try_from(self.as_slice()).map_err(|_| ShapeError::RankMismatch {
+            expected: N,
+            actual: self.rank(),
+        })?;
+        Ok(RuntimeShape::new(array))
+    }
+}
+
+impl<C: ShapeScalar> AbstractShape<C, DynArray<C>> for DynRankShape<C> {
+    #[inline]
+    fn size(&self) -> C {
+        self.size
+    }
+
+    #[inline]
+    fn linearize(&self, p: DynArray<C>) -> C {
+        self.linearize(&p)
+    }
+
+    #[inline]
+    fn delinearize(&self, i: C) -> DynArray<C> {
+        self.delinearize(i)
+    }
+}
+
+impl<C: ShapeScalar, const N: usize> From<&RuntimeShape<C, N>> for DynRankShape<C> {
+    fn from(shape: &RuntimeShape<C, N>) -> Self {
+        Self::new(&shape.as_array())
+    }
+}
+
+impl<C: ShapeScalar, const N: usize> TryFrom<&DynRankShape<C>> for RuntimeShape<C, N> {
+    type Error = ShapeError;
+
+    fn try_from(shape: &DynRankShape<C>) -> Result<Self, ShapeError> {
+        shape.to_fixed_rank()
+    }
+}
+
+/// An iterator over the points of a [`DynRankShape`] in linear order, yielding the linear index of each point along
+/// with its coordinates.
+///
+/// Returned by [`DynRankShape::iter_coords`].
+#[derive(Clone, Debug)]
+pub struct DynCoords<'a, C> {
+    shape: &'a DynRankShape<C>,
+    index: C,
+    next: Option<DynArray<C>>,
+}
+
+impl<C: ShapeScalar> Iterator for DynCoords<'_, C> {
+    type Item = (C, DynArray<C>);
+
+    fn next(&mut self) -> Option<(C, DynArray<C>)> {
+        let p = self.next.take()?;
+        let mut next = p.clone();
+        for (c, &dim) in next.iter_mut().zip(self.shape.as_slice()) {
+            *c = *c + C::ONE;
+            if *c < dim {
+                self.next = Some(next);
+                break;
+            }
+            *c = C::ZERO;
+        }
+        let index = self.index;
+        self.index = index + C::ONE;
+        Some((index, p))
+    }
+}
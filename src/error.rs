@@ -0,0 +1,62 @@
+use crate::Shape;
+
+use std::fmt;
+
+/// An error returned when a shape cannot be built from or applied to some other representation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ShapeError {
+    /// A buffer's length does not equal the number of elements in the shape.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The memory layout cannot be described by a row-major shape.
+    IncompatibleLayout,
+    /// A dimension or the total size does not fit in the coordinate type.
+    Overflow,
+    /// Two shapes that must have the same number of elements do not.
+    SizeMismatch,
+    /// A dimension is neither 1 nor equal to the corresponding broadcast target dimension.
+    NotBroadcastable,
+    /// A shape does not have the expected number of dimensions.
+    RankMismatch { expected: usize, actual: usize },
+    /// An axis that must have a dimension of 1 does not.
+    NotSingletonAxis { axis: usize },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "buffer length {actual} does not match shape size {expected}"
+            ),
+            Self::IncompatibleLayout => write!(f, "memory layout is not row-major"),
+            Self::Overflow => write!(f, "shape does not fit in the coordinate type"),
+            Self::SizeMismatch => write!(f, "shapes have different numbers of elements"),
+            Self::NotBroadcastable => {
+                write!(f, "shape cannot be broadcast to the target dimensions")
+            }
+            Self::RankMismatch { expected, actual } => write!(
+                f,
+                "shape has {actual} dimensions but {expected} were expected"
+            ),
+            Self::NotSingletonAxis { axis } => {
+                write!(f, "axis {axis} does not have a dimension of 1")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Returns [`ShapeError::LengthMismatch`] unless a buffer of length `actual` exactly fits `shape`.
+pub(crate) fn check_len<S: Shape<N>, const N: usize>(
+    shape: &S,
+    actual: usize,
+) -> Result<(), ShapeError> {
+    let expected = shape.usize();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(ShapeError::LengthMismatch { expected, actual })
+    }
+}
@@ -0,0 +1,135 @@
+//! [`AbstractShape`] implementations for [`glam`] integer vectors.
+//!
+//! Any [`Shape`] whose `Coord` matches the vector's scalar can linearize and delinearize that vector directly.
+//!
+//! ```
+//! use glam::{IVec3, U64Vec4, UVec2, UVec3};
+//! use ndshape::{AbstractShape, ConstPow2Shape3u32, ConstShape2u32, ConstShape3i32, RuntimeShape};
+//!
+//! let shape = ConstShape3i32::<5, 6, 7>;
+//! let p = IVec3::new(1, 2, 3);
+//! let index = AbstractShape::<i32, IVec3>::linearize(&shape, p);
+//! assert_eq!(index, 101);
+//! assert_eq!(AbstractShape::<i32, IVec3>::delinearize(&shape, index), p);
+//!
+//! let shape = ConstPow2Shape3u32::<1, 2, 3>;
+//! let p = UVec3::new(1, 2, 3);
+//! let index = AbstractShape::<u32, UVec3>::linearize(&shape, p);
+//! assert_eq!(index, 0b011_10_1);
+//! assert_eq!(AbstractShape::<u32, UVec3>::delinearize(&shape, index), p);
+//!
+//! let shape = ConstShape2u32::<5, 6>;
+//! let p = UVec2::new(4, 5);
+//! let index = AbstractShape::<u32, UVec2>::linearize(&shape, p);
+//! assert_eq!(AbstractShape::<u32, UVec2>::delinearize(&shape, index), p);
+//!
+//! let shape = RuntimeShape::<u64, 4>::new([5, 6, 7, 8]);
+//! let p = U64Vec4::new(4, 5, 6, 7);
+//! let index = AbstractShape::<u64, U64Vec4>::linearize(&shape, p);
+//! assert_eq!(index, 5 * 6 * 7 * 8 - 1);
+//! assert_eq!(AbstractShape::<u64, U64Vec4>::delinearize(&shape, index), p);
+//! ```
+
+use crate::{
+    AbstractShape, ConstPow2Shape2i32, ConstPow2Shape2i64, ConstPow2Shape2u32, ConstPow2Shape2u64,
+    ConstPow2Shape3i32, ConstPow2Shape3i64, ConstPow2Shape3u32, ConstPow2Shape3u64,
+    ConstPow2Shape4i32, ConstPow2Shape4i64, ConstPow2Shape4u32, ConstPow2Shape4u64, ConstShape2i32,
+    ConstShape2i64, ConstShape2u32, ConstShape2u64, ConstShape3i32, ConstShape3i64, ConstShape3u32,
+    ConstShape3u64, ConstShape4i32, ConstShape4i64, ConstShape4u32, ConstShape4u64,
+    RuntimePow2Shape, RuntimeShape, Shape,
+};
+
+use glam::{
+    I64Vec2, I64Vec3, I64Vec4, IVec2, IVec3, IVec4, U64Vec2, U64Vec3, U64Vec4, UVec2, UVec3, UVec4,
+};
+use static_assertions::assert_impl_all;
+
+macro_rules! impl_glam_shape {
+    ($vector:ty, $scalar:ty, $n:literal) => {
+        impl<S> AbstractShape<$scalar, $vector> for S
+        where
+            S: Shape<$n, Coord = $scalar>,
+        {
+            #[inline]
+            fn size(&self) -> $scalar {
+                Shape::size(self)
+            }
+
+            #[inline]
+            fn linearize(&self, p: $vector) -> $scalar {
+                Shape::linearize(self, p.to_array())
+            }
+
+            #[inline]
+            fn delinearize(&self, i: $scalar) -> $vector {
+                <$vector>::from_array(Shape::delinearize(self, i))
+            }
+        }
+    };
+}
+
+impl_glam_shape!(UVec2, u32, 2);
+impl_glam_shape!(UVec3, u32, 3);
+impl_glam_shape!(UVec4, u32, 4);
+impl_glam_shape!(U64Vec2, u64, 2);
+impl_glam_shape!(U64Vec3, u64, 3);
+impl_glam_shape!(U64Vec4, u64, 4);
+
+impl_glam_shape!(IVec2, i32, 2);
+impl_glam_shape!(IVec3, i32, 3);
+impl_glam_shape!(IVec4, i32, 4);
+impl_glam_shape!(I64Vec2, i64, 2);
+impl_glam_shape!(I64Vec3, i64, 3);
+impl_glam_shape!(I64Vec4, i64, 4);
+
+assert_impl_all!(ConstShape2u32<1, 1>: AbstractShape<u32, UVec2>);
+assert_impl_all!(ConstShape3u32<1, 1, 1>: AbstractShape<u32, UVec3>);
+assert_impl_all!(ConstShape4u32<1, 1, 1, 1>: AbstractShape<u32, UVec4>);
+assert_impl_all!(ConstShape2u64<1, 1>: AbstractShape<u64, U64Vec2>);
+assert_impl_all!(ConstShape3u64<1, 1, 1>: AbstractShape<u64, U64Vec3>);
+assert_impl_all!(ConstShape4u64<1, 1, 1, 1>: AbstractShape<u64, U64Vec4>);
+assert_impl_all!(ConstShape2i32<1, 1>: AbstractShape<i32, IVec2>);
+assert_impl_all!(ConstShape3i32<1, 1, 1>: AbstractShape<i32, IVec3>);
+assert_impl_all!(ConstShape4i32<1, 1, 1, 1>: AbstractShape<i32, IVec4>);
+assert_impl_all!(ConstShape2i64<1, 1>: AbstractShape<i64, I64Vec2>);
+assert_impl_all!(ConstShape3i64<1, 1, 1>: AbstractShape<i64, I64Vec3>);
+assert_impl_all!(ConstShape4i64<1, 1, 1, 1>: AbstractShape<i64, I64Vec4>);
+
+assert_impl_all!(ConstPow2Shape2u32<1, 1>: AbstractShape<u32, UVec2>);
+assert_impl_all!(ConstPow2Shape3u32<1, 1, 1>: AbstractShape<u32, UVec3>);
+assert_impl_all!(ConstPow2Shape4u32<1, 1, 1, 1>: AbstractShape<u32, UVec4>);
+assert_impl_all!(ConstPow2Shape2u64<1, 1>: AbstractShape<u64, U64Vec2>);
+assert_impl_all!(ConstPow2Shape3u64<1, 1, 1>: AbstractShape<u64, U64Vec3>);
+assert_impl_all!(ConstPow2Shape4u64<1, 1, 1, 1>: AbstractShape<u64, U64Vec4>);
+assert_impl_all!(ConstPow2Shape2i32<1, 1>: AbstractShape<i32, IVec2>);
+assert_impl_all!(ConstPow2Shape3i32<1, 1, 1>: AbstractShape<i32, IVec3>);
+assert_impl_all!(ConstPow2Shape4i32<1, 1, 1, 1>: AbstractShape<i32, IVec4>);
+assert_impl_all!(ConstPow2Shape2i64<1, 1>: AbstractShape<i64, I64Vec2>);
+assert_impl_all!(ConstPow2Shape3i64<1, 1, 1>: AbstractShape<i64, I64Vec3>);
+assert_impl_all!(ConstPow2Shape4i64<1, 1, 1, 1>: AbstractShape<i64, I64Vec4>);
+
+assert_impl_all!(RuntimeShape<u32, 2>: AbstractShape<u32, UVec2>);
+assert_impl_all!(RuntimeShape<u32, 3>: AbstractShape<u32, UVec3>);
+assert_impl_all!(RuntimeShape<u32, 4>: AbstractShape<u32, UVec4>);
+assert_impl_all!(RuntimeShape<u64, 2>: AbstractShape<u64, U64Vec2>);
+assert_impl_all!(RuntimeShape<u64, 3>: AbstractShape<u64, U64Vec3>);
+assert_impl_all!(RuntimeShape<u64, 4>: AbstractShape<u64, U64Vec4>);
+assert_impl_all!(RuntimeShape<i32, 2>: AbstractShape<i32, IVec2>);
+assert_impl_all!(RuntimeShape<i32, 3>: AbstractShape<i32, IVec3>);
+assert_impl_all!(RuntimeShape<i32, 4>: AbstractShape<i32, IVec4>);
+assert_impl_all!(RuntimeShape<i64, 2>: AbstractShape<i64, I64Vec2>);
+assert_impl_all!(RuntimeShape<i64, 3>: AbstractShape<i64, I64Vec3>);
+assert_impl_all!(RuntimeShape<i64, 4>: AbstractShape<i64, I64Vec4>);
+
+assert_impl_all!(RuntimePow2Shape<u32, 2>: AbstractShape<u32, UVec2>);
+assert_impl_all!(RuntimePow2Shape<u32, 3>: AbstractShape<u32, UVec3>);
+assert_impl_all!(RuntimePow2Shape<u32, 4>: AbstractShape<u32, UVec4>);
+assert_impl_all!(RuntimePow2Shape<u64, 2>: AbstractShape<u64, U64Vec2>);
+assert_impl_all!(RuntimePow2Shape<u64, 3>: AbstractShape<u64, U64Vec3>);
+assert_impl_all!(RuntimePow2Shape<u64, 4>: AbstractShape<u64, U64Vec4>);
+assert_impl_all!(RuntimePow2Shape<i32, 2>: AbstractShape<i32, IVec2>);
+assert_impl_all!(RuntimePow2Shape<i32, 3>: AbstractShape<i32, IVec3>);
+assert_impl_all!(RuntimePow2Shape<i32, 4>: AbstractShape<i32, IVec4>);
+assert_impl_all!(RuntimePow2Shape<i64, 2>: AbstractShape<i64, I64Vec2>);
+assert_impl_all!(RuntimePow2Shape<i64, 3>: AbstractShape<i64, I64Vec3>);
+assert_impl_all!(RuntimePow2Shape<i64, 4>: AbstractShape<i64, I64Vec4>);
@@ -0,0 +1,165 @@
+use crate::scalar::{is_negative, magnitude};
+use crate::{Shape, ShapeScalar};
+
+use core::marker::PhantomData;
+
+/// An iterator over the points of a box in row-major order, yielding the linear index of each point in a shape along
+/// with its coordinates.
+///
+/// Only coordinates are stepped, so there is no division per point.
+#[derive(Clone, Debug)]
+pub struct BoxIter<'a, S: ?Sized, C, const N: usize> {
+    shape: &'a S,
+    min: [C; N],
+    max: [C; N],
+    next: Option<[C; N]>,
+}
+
+impl<'a, S: ?Sized, C, const N: usize> BoxIter<'a, S, C, N>
+where
+    S: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    /// Iterates over the box starting at `min` with dimensions `box_shape`.
+    pub fn new(shape: &'a S, min: [C; N], box_shape: [C; N]) -> Self {
+        let mut max = min;
+        for i in 0..N {
+            max[i] = min[i] + box_shape[i];
+        }
+        let is_empty = box_shape.iter().any(|&d| d <= C::ZERO);
+        Self {
+            shape,
+            min,
+            max,
+            next: (!is_empty).then_some(min),
+        }
+    }
+}
+
+impl<S: ?Sized, C, const N: usize> Iterator for BoxIter<'_, S, C, N>
+where
+    S: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    type Item = (C, [C; N]);
+
+    #[inline]
+    fn next(&mut self) -> Option<(C, [C; N])> {
+        let p = self.next?;
+        let mut next = p;
+        self.next = None;
+        for i in 0..N {
+            next[i] = next[i] + C::ONE;
+            if next[i] < self.max[i] {
+                self.next = Some(next);
+                break;
+            }
+            next[i] = self.min[i];
+        }
+        Some((self.shape.linearize(p), p))
+    }
+}
+
+/// One of the two faces of a shape perpendicular to an axis.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Side {
+    /// The face where the coordinate along the axis is 0.
+    Min,
+    /// The face where the coordinate along the axis is the dimension minus 1.
+    Max,
+}
+
+/// An iterator over the points on the outer faces of a shape in increasing linear order, yielding the linear index of
+/// each point along with its coordinates.
+///
+/// The points are gathered by walking the axes from the smallest stride to the largest, in the direction of increasing
+/// linear index, and rows in the interior of the shape are crossed in a single jump, so only boundary points are
+/// visited. That walk is already in linear order for shapes whose index is a sum of strided coordinates, and the points
+/// are sorted by linear index for any other layout.
+#[derive(Clone, Debug)]
+pub struct BoundaryIter<'a, S: ?Sized, C, const N: usize> {
+    points: std::vec::IntoIter<(C, [C; N])>,
+    marker: PhantomData<&'a S>,
+}
+
+impl<'a, S: ?Sized, C, const N: usize> BoundaryIter<'a, S, C, N>
+where
+    S: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    pub fn new(shape: &'a S) -> Self {
+        let array = shape.as_array();
+        let is_empty = N == 0 || array.iter().any(|&d| d <= C::ZERO);
+        let mut points = Vec::new();
+        if !is_empty {
+            let strides = shape.strides();
+            let mut order = [0; N];
+            for (i, axis) in order.iter_mut().enumerate() {
+                *axis = i;
+            }
+            order.sort_by_key(|&axis| magnitude(strides[axis]));
+
+            // Steps along the axes in `order`, counted in the direction of increasing linear index.
+            let mut steps = [C::ZERO; N];
+            'walk: loop {
+                let mut p = [C::ZERO; N];
+                for (&axis, &step) in order.iter().zip(steps.iter()) {
+                    p[axis] = if is_negative(strides[axis]) {
+                        array[axis] - C::ONE - step
+                    } else {
+                        step
+                    };
+                }
+                points.push((shape.linearize(p), p));
+
+                let mut j = 0;
+                loop {
+                    if j == N {
+                        break 'walk;
+                    }
+                    steps[j] = steps[j] + C::ONE;
+                    if steps[j] < array[order[j]] {
+                        break;
+                    }
+                    steps[j] = C::ZERO;
+                    j += 1;
+                }
+
+                // Jump over the interior of a row that is not on any face.
+                let last = array[order[0]] - C::ONE;
+                let is_interior_row = order
+                    .iter()
+                    .zip(steps.iter())
+                    .skip(1)
+                    .all(|(&axis, &step)| C::ZERO < step && step + C::ONE < array[axis]);
+                if steps[0] > C::ZERO && steps[0] < last && is_interior_row {
+                    steps[0] = last;
+                }
+            }
+            // A stable sort only checks the order of points that are already sorted.
+            points.sort_by_key(|&(i, _)| i);
+        }
+        Self {
+            points: points.into_iter(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S: ?Sized, C, const N: usize> Iterator for BoundaryIter<'_, S, C, N>
+where
+    S: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    type Item = (C, [C; N]);
+
+    #[inline]
+    fn next(&mut self) -> Option<(C, [C; N])> {
+        self.points.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
@@ -57,7 +57,7 @@
 //!
 //! It is often beneficial to linearize a negative vector that results in a negative linear "stride." But when using unsigned
 //! linear indices, a negative stride would require a modular arithmetic representation, where e.g. `-1` maps to `u32::MAX`.
-//! This works fine with any [`Shape`](crate::Shape). You just need to be sure to use modular arithmetic with the resulting
+//! This works fine with any [`Shape`]. You just need to be sure to use modular arithmetic with the resulting
 //! linear strides, e.g. [`u32::wrapping_add`](u32::wrapping_add) and [`u32::wrapping_mul`](u32::wrapping_mul). Also, it is not
 //! possible to delinearize a negative stride with modular arithmetic. For that, you must use signed integer coordinates.
 //!
@@ -79,12 +79,87 @@
 //! // Delinearize works with signed coordinates.
 //! assert_eq!(shape.delinearize(stride), [0, -1, 0]);
 //! ```
+//!
+//! # Example: 128-bit Index Spaces
+//!
+//! Every shape is also available with `u128` and `i128` coordinates, for index spaces that don't fit in 64 bits.
+//!
+//! ```
+//! use ndshape::{Shape, ConstPow2Shape4u128, RuntimeShape};
+//!
+//! let shape = ConstPow2Shape4u128::<20, 20, 20, 20>;
+//! let p = [1, 2, 3, 4];
+//! let index = shape.linearize(p);
+//! assert_eq!(index, 1 + (2 << 20) + (3 << 40) + (4 << 60));
+//! assert_eq!(shape.delinearize(index), p);
+//!
+//! let shape = RuntimeShape::<i128, 4>::new([1 << 20; 4]);
+//! let index = shape.linearize([5, 6, 7, 8]);
+//! assert_eq!(index, 5 + (6 << 20) + (7 << 40) + (8 << 60));
+//! assert_eq!(shape.delinearize(index), [5, 6, 7, 8]);
+//! ```
+//!
+//! # Crate Features
+//!
+//! - `glam`: Implements [`AbstractShape`] for `glam` integer vectors like `UVec3` and `IVec3`, so
+//!   they can be linearized without converting to arrays first.
+//! - `mint`: Implements [`AbstractShape`] for `mint` vectors of every supported scalar, which gives
+//!   any math library with `mint` conversions (e.g. `nalgebra` or `cgmath`) a zero-cost path into linearization.
+//! - `nalgebra`: Implements [`AbstractShape`] for `nalgebra` fixed-size vectors and points, and
+//!   adds conversions from dimension vectors into [`RuntimeShape`].
+//! - `ndarray`: Adds `array_view` and `array_view_mut` for viewing a buffer through any [`Shape`] as an `ndarray`
+//!   array, and conversions from standard-layout arrays into a [`RuntimeShape`].
+//! - `rayon`: Adds the `ParallelShape` extension trait for iterating over the points of any [`Shape`] in parallel,
+//!   split into contiguous rows and slabs.
 
+mod chunked_layout;
 mod const_shape;
+mod cursor;
+mod decompose;
+mod dyn_rank_shape;
+mod error;
+#[cfg(feature = "glam")]
+mod glam_impls;
+mod iter;
+mod lod;
+#[cfg(feature = "mint")]
+mod mint_impls;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls;
+#[cfg(feature = "ndarray")]
+mod ndarray_impls;
+#[cfg(feature = "rayon")]
+mod par_iter;
 mod runtime_shape;
+mod scalar;
+mod shaped_array;
+mod stencil;
+mod strided_shape;
+mod transform;
+mod wide_shape;
+mod window;
 
+pub use chunked_layout::*;
 pub use const_shape::*;
+pub use cursor::*;
+pub use dyn_rank_shape::*;
+pub use error::*;
+pub use iter::*;
+pub use lod::LodShape;
+#[cfg(feature = "ndarray")]
+pub use ndarray_impls::*;
+#[cfg(feature = "rayon")]
+pub use par_iter::*;
 pub use runtime_shape::*;
+pub use scalar::*;
+pub use shaped_array::*;
+pub use stencil::*;
+pub use strided_shape::*;
+pub use transform::*;
+pub use wide_shape::*;
+pub use window::*;
+
+use core::ops::Range;
 
 /// The shape of an array with unspecified dimensionality.
 pub trait AbstractShape<Coord, Vector> {
@@ -99,7 +174,7 @@ pub trait AbstractShape<Coord, Vector> {
 
 /// The shape of an `N`-dimensional array.
 pub trait Shape<const N: usize> {
-    type Coord;
+    type Coord: ShapeScalar;
 
     /// The number of elements in an array with this shape.
     fn size(&self) -> Self::Coord;
@@ -109,13 +184,489 @@ pub trait Shape<const N: usize> {
     fn as_array(&self) -> [Self::Coord; N];
     /// Translate an `N`-dimensional vector into a single number `T` that can be used for linear indexing.
     fn linearize(&self, p: [Self::Coord; N]) -> Self::Coord;
-    /// The inverse of `linearize`.
+    /// The inverse of `linearize`. Never divides by zero, even if the shape is empty, in which case the result is
+    /// meaningless.
     fn delinearize(&self, i: Self::Coord) -> [Self::Coord; N];
+
+    /// Whether the shape has no elements, i.e. some dimension is zero.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// assert!(ConstShape3u32::<5, 0, 7>.is_empty());
+    /// assert!(!ConstShape3u32::<5, 6, 7>.is_empty());
+    /// ```
+    fn is_empty(&self) -> bool {
+        self.as_array().iter().any(|&dim| dim <= Self::Coord::ZERO)
+    }
+
+    /// [`Self::delinearize`], or `None` if `i` is not in `0..self.size()`, which is always the case for an empty shape.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// assert_eq!(shape.try_delinearize(101), Some([1, 2, 3]));
+    /// assert_eq!(shape.try_delinearize(210), None);
+    /// assert_eq!(ConstShape3u32::<5, 0, 7>.try_delinearize(0), None);
+    /// ```
+    fn try_delinearize(&self, i: Self::Coord) -> Option<[Self::Coord; N]> {
+        (Self::Coord::ZERO <= i && i < self.size()).then(|| self.delinearize(i))
+    }
+
+    /// The linear distance between neighboring elements along each axis, i.e. `linearize` of each unit vector minus
+    /// `linearize` of the origin.
+    ///
+    /// The default measures this with `linearize`, which is correct for any affine layout. The shapes in this crate
+    /// override it with strides they already know.
+    ///
+    /// ```
+    /// use ndshape::{ConstPow2Shape3u32, ConstShape3u32, RuntimeShape, Shape};
+    ///
+    /// assert_eq!(ConstShape3u32::<5, 6, 7>.strides(), [1, 5, 30]);
+    /// assert_eq!(ConstPow2Shape3u32::<1, 2, 3>.strides(), [1, 2, 8]);
+    /// assert_eq!(RuntimeShape::<u32, 3>::new([5, 6, 7]).strides(), [1, 5, 30]);
+    /// assert_eq!(ConstShape3u32::<5, 6, 7>.slice_axis::<2>(0, 1).strides(), [5, 30]);
+    ///
+    /// // A column-major shape defined outside of this crate.
+    /// struct ColumnMajor([u32; 3]);
+    ///
+    /// impl Shape<3> for ColumnMajor {
+    ///     type Coord = u32;
+    ///
+    ///     fn size(&self) -> u32 {
+    ///         self.0.iter().product()
+    ///     }
+    ///     fn usize(&self) -> usize {
+    ///         self.size() as usize
+    ///     }
+    ///     fn as_array(&self) -> [u32; 3] {
+    ///         self.0
+    ///     }
+    ///     fn linearize(&self, [x, y, z]: [u32; 3]) -> u32 {
+    ///         let [_, dy, dz] = self.0;
+    ///         z + dz * (y + dy * x)
+    ///     }
+    ///     fn delinearize(&self, i: u32) -> [u32; 3] {
+    ///         let [_, dy, dz] = self.0;
+    ///         [i / (dy * dz), i / dz % dy, i % dz]
+    ///     }
+    /// }
+    ///
+    /// let shape = ColumnMajor([5, 6, 7]);
+    /// assert_eq!(shape.strides(), [42, 7, 1]);
+    /// assert_eq!(shape.slice_axis::<2>(0, 2).linearize([3, 4]), shape.linearize([2, 3, 4]));
+    /// ```
+    fn strides(&self) -> [Self::Coord; N] {
+        let origin = self.linearize([Self::Coord::ZERO; N]);
+        let mut strides = [Self::Coord::ZERO; N];
+        for (k, stride) in strides.iter_mut().enumerate() {
+            let mut unit = [Self::Coord::ZERO; N];
+            unit[k] = Self::Coord::ONE;
+            *stride = self.linearize(unit).wrapping_sub(origin);
+        }
+        strides
+    }
+
+    /// Whether stepping along `axis` moves to the next linear index, or `axis` only has one element.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 1>;
+    /// assert!(shape.is_contiguous_axis(0));
+    /// assert!(!shape.is_contiguous_axis(1));
+    /// assert!(shape.is_contiguous_axis(2));
+    /// assert!(shape.slice_axis::<2>(0, 1).is_contiguous_axis(1));
+    /// ```
+    fn is_contiguous_axis(&self, axis: usize) -> bool {
+        self.strides()[axis] == Self::Coord::ONE || self.as_array()[axis] <= Self::Coord::ONE
+    }
+
+    /// The `M = N - 1` dimensional plane at `index` along `axis`. Linearizing a point on the plane gives the linear
+    /// index of the corresponding point in `self`, so the plane can index the same buffer.
+    ///
+    /// Panics if `axis` is not less than `N` or `index` is outside of the axis.
+    ///
+    /// ```
+    /// use ndshape::{ConstPow2Shape3u32, ConstShape3u32, RuntimeShape, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// let plane = shape.slice_axis(2, 3);
+    /// assert_eq!(plane.as_array(), [5, 6]);
+    /// assert_eq!(plane.linearize([1, 2]), shape.linearize([1, 2, 3]));
+    /// assert_eq!(plane.delinearize(shape.linearize([1, 2, 3])), [1, 2]);
+    ///
+    /// let shape = RuntimeShape::<i32, 3>::new([5, 6, 7]);
+    /// let plane = shape.slice_axis(1, 4);
+    /// assert_eq!(plane.as_array(), [5, 7]);
+    /// assert_eq!(plane.linearize([3, 6]), shape.linearize([3, 4, 6]));
+    /// assert_eq!(plane.delinearize(plane.linearize([3, 6])), [3, 6]);
+    ///
+    /// let shape = ConstPow2Shape3u32::<1, 2, 3>;
+    /// let line = shape.slice_axis::<2>(0, 1).slice_axis::<1>(1, 5);
+    /// assert_eq!(line.as_array(), [4]);
+    /// assert_eq!(line.linearize([2]), shape.linearize([1, 2, 5]));
+    /// ```
+    ///
+    /// ```should_panic
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// // Axis 2 only has 7 planes.
+    /// ConstShape3u32::<5, 6, 7>.slice_axis::<2>(2, 100);
+    /// ```
+    fn slice_axis<const M: usize>(
+        &self,
+        axis: usize,
+        index: Self::Coord,
+    ) -> StridedShape<Self::Coord, M> {
+        strided_shape::slice_axis(
+            self.as_array(),
+            self.strides(),
+            self.linearize([Self::Coord::ZERO; N]),
+            axis,
+            index,
+        )
+    }
+
+    /// A view of an array with this shape as if it had the `target` dimensions, following NumPy's broadcasting rules.
+    ///
+    /// Axes are matched up starting from axis 0, which is the contiguous axis like NumPy's trailing axis. Each dimension
+    /// must either equal the target dimension or be 1, in which case it gets a stride of zero. The `M - N` missing
+    /// higher axes also get a stride of zero. Otherwise [`ShapeError::NotBroadcastable`] is returned.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape2u32, ConstShape3u32, RuntimeShape, Shape, ShapeError};
+    ///
+    /// let volume = ConstShape3u32::<5, 6, 7>;
+    ///
+    /// // A per-column 2D map, repeated along Z.
+    /// let map = ConstShape2u32::<5, 6>;
+    /// let view = map.broadcast_to(volume.as_array()).unwrap();
+    /// assert_eq!(view.size(), volume.size());
+    /// assert_eq!(view.linearize([1, 2, 3]), map.linearize([1, 2]));
+    ///
+    /// // A per-slice 1D profile, repeated along X and Y.
+    /// let profile = RuntimeShape::<u32, 3>::new([1, 1, 7]);
+    /// let view = profile.broadcast_to([5, 6, 7]).unwrap();
+    /// assert_eq!(view.linearize([1, 2, 3]), 3);
+    ///
+    /// assert_eq!(map.broadcast_to([5, 3, 7]).err(), Some(ShapeError::NotBroadcastable));
+    /// ```
+    fn broadcast_to<const M: usize>(
+        &self,
+        target: [Self::Coord; M],
+    ) -> Result<StridedShape<Self::Coord, M>, ShapeError> {
+        strided_shape::broadcast(
+            self.as_array(),
+            self.strides(),
+            self.linearize([Self::Coord::ZERO; N]),
+            target,
+        )
+    }
+
+    /// A view of every `step`-th point starting at `start`, with `count` points along each axis. Coordinate `k` of the
+    /// view maps to `start + k * step` in `self`, and linearizing gives indices into the buffer of `self`.
+    ///
+    /// Panics if the last point along some axis is outside of `self`.
+    ///
+    /// ```
+    /// use ndshape::{ConstPow2Shape3u32, ConstShape3u32, Shape};
+    ///
+    /// // Decimate by 2.
+    /// let shape = ConstShape3u32::<8, 8, 8>;
+    /// let half = shape.step_view([0, 0, 0], [2, 2, 2], [4, 4, 4]);
+    /// assert_eq!(half.as_array(), [4, 4, 4]);
+    /// assert_eq!(half.linearize([1, 2, 3]), shape.linearize([2, 4, 6]));
+    /// assert_eq!(half.delinearize(shape.linearize([2, 4, 6])), [1, 2, 3]);
+    ///
+    /// // The odd sub-lattice of a power-of-two shape.
+    /// let shape = ConstPow2Shape3u32::<3, 3, 3>;
+    /// let odd = shape.step_view([1, 1, 1], [2, 2, 2], [4, 4, 4]);
+    /// assert_eq!(odd.linearize([3, 0, 2]), shape.linearize([7, 1, 5]));
+    ///
+    /// // Views compose.
+    /// let quarter = half.step_view([1, 1, 1], [2, 2, 2], [2, 2, 2]);
+    /// assert_eq!(quarter.linearize([1, 1, 1]), ConstShape3u32::<8, 8, 8>.linearize([6, 6, 6]));
+    /// ```
+    ///
+    /// Negative steps walk backwards, but must not pass the origin. Like the strides of [`Flipped`], they may be wrapped
+    /// values with unsigned coordinates.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// let shape = ConstShape3u32::<8, 8, 8>;
+    /// let reversed = shape.step_view([7, 0, 0], [u32::MAX, 1, 1], [4, 1, 1]);
+    /// assert_eq!(reversed.linearize([3, 0, 0]), shape.linearize([4, 0, 0]));
+    /// assert_eq!(reversed.delinearize(shape.linearize([4, 0, 0])), [3, 0, 0]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use ndshape::{RuntimeShape, Shape};
+    ///
+    /// let shape = RuntimeShape::<i32, 2>::new([8, 8]);
+    /// let reversed = shape.step_view([7, 0], [-1, 1], [8, 8]);
+    /// assert_eq!(reversed.linearize([7, 0]), 0);
+    ///
+    /// // The fourth point along axis 0 would be at -1.
+    /// shape.step_view([2, 0], [-1, 1], [4, 8]);
+    /// ```
+    fn step_view(
+        &self,
+        start: [Self::Coord; N],
+        step: [Self::Coord; N],
+        count: [Self::Coord; N],
+    ) -> StridedShape<Self::Coord, N> {
+        let array = self.as_array();
+        let mut strides = self.strides();
+        for k in 0..N {
+            if count[k] > Self::Coord::ZERO {
+                // The distance to the last point, which must stay on the side of `start` that `step` walks towards.
+                let span = (count[k] - Self::Coord::ONE).checked_mul(scalar::magnitude(step[k]));
+                let in_bounds = Self::Coord::ZERO <= start[k]
+                    && start[k] < array[k]
+                    && span.is_some_and(|span| {
+                        Self::Coord::ZERO <= span
+                            && if scalar::is_negative(step[k]) {
+                                span <= start[k]
+                            } else {
+                                span < array[k] - start[k]
+                            }
+                    });
+                assert!(in_bounds, "stepped axis {k} is out of bounds");
+            }
+            strides[k] = strides[k].wrapping_mul(step[k]);
+        }
+        StridedShape::new(count, strides, self.linearize(start))
+    }
+
+    /// All points on the outer faces of the shape, each visited once in increasing linear order. See [`BoundaryIter`].
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Flipped, Rotated, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// let boundary: Vec<_> = shape.iter_boundary().collect();
+    /// assert_eq!(boundary.len(), 5 * 6 * 7 - 3 * 4 * 5);
+    /// assert!(boundary.windows(2).all(|w| w[0].0 < w[1].0));
+    /// assert!(boundary.iter().all(|&(i, _)| shape.is_boundary(i)));
+    /// assert!(!shape.is_boundary(shape.linearize([1, 1, 1])));
+    ///
+    /// let flipped = Flipped::new(shape, [true, false, true]);
+    /// let rotated = Rotated::new(shape, [0, 1], 1);
+    /// for indices in [
+    ///     flipped.iter_boundary().map(|(i, _)| i).collect::<Vec<_>>(),
+    ///     rotated.iter_boundary().map(|(i, _)| i).collect(),
+    /// ] {
+    ///     assert_eq!(indices.len(), boundary.len());
+    ///     assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    /// }
+    /// ```
+    fn iter_boundary(&self) -> BoundaryIter<'_, Self, Self::Coord, N> {
+        BoundaryIter::new(self)
+    }
+
+    /// All points on the face perpendicular to `axis` on the given `side`, in row-major order.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape, Side};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// let face: Vec<_> = shape.iter_face(2, Side::Max).collect();
+    /// assert_eq!(face.len(), 5 * 6);
+    /// assert!(face.iter().all(|&(_, p)| p[2] == 6));
+    /// assert!(shape.iter_face(0, Side::Min).all(|(_, p)| p[0] == 0));
+    /// ```
+    fn iter_face(&self, axis: usize, side: Side) -> BoxIter<'_, Self, Self::Coord, N> {
+        let mut min = [Self::Coord::ZERO; N];
+        let mut face_shape = self.as_array();
+        if face_shape[axis] > Self::Coord::ZERO {
+            if side == Side::Max {
+                min[axis] = face_shape[axis] - Self::Coord::ONE;
+            }
+            face_shape[axis] = Self::Coord::ONE;
+        }
+        BoxIter::new(self, min, face_shape)
+    }
+
+    /// The neighborhoods of a `kernel`-shaped window centered on each point of the shape, with neighbors outside of the
+    /// shape handled according to `mode`. See [`Windows`].
+    ///
+    /// ```
+    /// use ndshape::{BoundaryMode, ConstShape2u32, RuntimeShape, Shape};
+    ///
+    /// let shape = RuntimeShape::<u32, 2>::new([6, 6]);
+    /// let windows = shape.iter_windows(ConstShape2u32::<5, 5>, BoundaryMode::Skip);
+    /// assert_eq!(windows.iter().count(), 2 * 2);
+    /// assert!(windows.iter().all(|w| w.indices().len() == 25));
+    /// ```
+    fn iter_windows<K>(&self, kernel: K, mode: BoundaryMode) -> Windows<'_, Self, K, Self::Coord, N>
+    where
+        K: Shape<N, Coord = Self::Coord>,
+    {
+        Windows::new(self, kernel, mode)
+    }
+
+    /// [`Shape::iter_windows`] for a [`ConstShape`] kernel, with the offsets in an array of `LEN = K::USIZE` entries so
+    /// that visiting the neighbors of an interior center is a loop with a constant trip count. Fails to compile unless
+    /// `LEN == K::USIZE`.
+    ///
+    /// ```
+    /// use ndshape::{BoundaryMode, ConstShape3u32, RuntimeShape, Shape};
+    ///
+    /// let shape = RuntimeShape::<u32, 3>::new([8, 8, 8]);
+    /// let kernel = ConstShape3u32::<3, 3, 3>;
+    /// let windows = shape.iter_const_windows::<_, 27>(kernel, BoundaryMode::Clamp);
+    /// let dynamic = shape.iter_windows(kernel, BoundaryMode::Clamp);
+    /// assert_eq!(windows.offsets(), dynamic.offsets());
+    /// for (w, d) in windows.iter().zip(&dynamic) {
+    ///     let mut indices = Vec::new();
+    ///     w.for_each_index(|_, i| indices.push(i));
+    ///     assert!(indices.into_iter().eq(d.indices()));
+    /// }
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use ndshape::{BoundaryMode, ConstShape2u32, Shape};
+    ///
+    /// let shape = ConstShape2u32::<8, 8>;
+    /// shape.iter_const_windows::<_, 8>(ConstShape2u32::<3, 3>, BoundaryMode::Skip);
+    /// ```
+    fn iter_const_windows<K, const LEN: usize>(
+        &self,
+        kernel: K,
+        mode: BoundaryMode,
+    ) -> Windows<'_, Self, K, Self::Coord, N, [Self::Coord; LEN]>
+    where
+        K: ConstShape<N, Coord = Self::Coord>,
+    {
+        Windows::new_const(self, kernel, mode)
+    }
+
+    /// Whether the point at linear index `i` is on one of the outer faces of the shape.
+    fn is_boundary(&self, i: Self::Coord) -> bool {
+        self.delinearize(i)
+            .iter()
+            .zip(self.as_array())
+            .any(|(&c, dim)| c == Self::Coord::ZERO || c + Self::Coord::ONE == dim)
+    }
+
+    /// The minimal list of `(min, shape)` boxes whose union is exactly the linear index `range`, in increasing linear
+    /// order: a partial row, then partial slabs, whole slabs, and so on, back down to a partial row.
+    ///
+    /// Returns [`ShapeError::IncompatibleLayout`] unless this shape is row-major, since e.g. a Morton layout would not
+    /// decompose the same way. Panics if `range` is not within `0..self.size()`.
+    ///
+    /// ```
+    /// use ndshape::{BoxIter, ConstShape3u32, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// let boxes = shape.linear_range_to_boxes(13..101).unwrap();
+    /// assert_eq!(
+    ///     boxes,
+    ///     [
+    ///         ([3, 2, 0], [2, 1, 1]),
+    ///         ([0, 3, 0], [5, 3, 1]),
+    ///         ([0, 0, 1], [5, 6, 2]),
+    ///         ([0, 0, 3], [5, 2, 1]),
+    ///         ([0, 2, 3], [1, 1, 1]),
+    ///     ]
+    /// );
+    ///
+    /// let indices: Vec<u32> = boxes
+    ///     .iter()
+    ///     .flat_map(|&(min, box_shape)| BoxIter::new(&shape, min, box_shape).map(|(i, _)| i))
+    ///     .collect();
+    /// assert_eq!(indices, (13..101).collect::<Vec<_>>());
+    ///
+    /// let plane = shape.slice_axis::<2>(1, 3);
+    /// assert!(plane.linear_range_to_boxes(0..5).is_err());
+    ///
+    /// // A column-major shape defined outside of this crate.
+    /// struct ColumnMajor([u32; 2]);
+    ///
+    /// impl Shape<2> for ColumnMajor {
+    ///     type Coord = u32;
+    ///
+    ///     fn size(&self) -> u32 {
+    ///         self.0[0] * self.0[1]
+    ///     }
+    ///     fn usize(&self) -> usize {
+    ///         self.size() as usize
+    ///     }
+    ///     fn as_array(&self) -> [u32; 2] {
+    ///         self.0
+    ///     }
+    ///     fn linearize(&self, [x, y]: [u32; 2]) -> u32 {
+    ///         y + self.0[1] * x
+    ///     }
+    ///     fn delinearize(&self, i: u32) -> [u32; 2] {
+    ///         [i / self.0[1], i % self.0[1]]
+    ///     }
+    /// }
+    ///
+    /// assert!(ColumnMajor([5, 6]).linear_range_to_boxes(0..5).is_err());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn linear_range_to_boxes(
+        &self,
+        range: Range<Self::Coord>,
+    ) -> Result<Vec<([Self::Coord; N], [Self::Coord; N])>, ShapeError> {
+        let array = self.as_array();
+        let row_major = strided_shape::row_major_strides(array);
+        let is_row_major = self.linearize([Self::Coord::ZERO; N]) == Self::Coord::ZERO
+            && (0..N).all(|k| {
+                let mut unit = [Self::Coord::ZERO; N];
+                unit[k] = Self::Coord::ONE;
+                self.linearize(unit) == row_major[k]
+            });
+        if !is_row_major {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        Ok(decompose::linear_range_to_boxes(array, range))
+    }
+
+    /// The minimal list of `(start, len)` runs of consecutive linear indices that cover the box starting at `min` with
+    /// dimensions `box_shape`. Rows and slabs that are contiguous in memory are merged, so a box spanning the full width
+    /// of every axis but the last is a single run.
+    ///
+    /// ```
+    /// use ndshape::{ConstPow2Shape3u32, ConstShape2u32, ConstShape3i32, ConstShape3u32, Flipped, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// assert_eq!(
+    ///     shape.box_to_runs([1, 2, 3], [2, 2, 2]),
+    ///     [(101, 2), (106, 2), (131, 2), (136, 2)]
+    /// );
+    /// assert_eq!(shape.box_to_runs([0, 2, 3], [5, 2, 2]), [(100, 10), (130, 10)]);
+    /// assert_eq!(shape.box_to_runs([0, 0, 3], [5, 6, 2]), [(90, 60)]);
+    ///
+    /// let shape = ConstPow2Shape3u32::<2, 2, 2>;
+    /// assert_eq!(shape.box_to_runs([0, 0, 1], [4, 4, 3]), [(16, 48)]);
+    ///
+    /// // Strided views merge what they can.
+    /// let plane = ConstShape3u32::<5, 6, 7>.slice_axis::<2>(1, 2);
+    /// assert_eq!(plane.box_to_runs([0, 0], [5, 2]), [(10, 5), (40, 5)]);
+    ///
+    /// // Reversed and broadcast axes are left out of the runs.
+    /// let flipped = Flipped::new(ConstShape3i32::<5, 6, 7>, [false, false, true]);
+    /// assert_eq!(flipped.box_to_runs([0, 0, 0], [5, 6, 7]).len(), 7);
+    /// let view = ConstShape2u32::<5, 6>.broadcast_to([5, 6, 7]).unwrap();
+    /// assert_eq!(view.box_to_runs([0, 0, 0], [5, 6, 7]).len(), 7);
+    /// ```
+    fn box_to_runs(
+        &self,
+        min: [Self::Coord; N],
+        box_shape: [Self::Coord; N],
+    ) -> Vec<(Self::Coord, Self::Coord)> {
+        decompose::box_to_runs(self, self.strides(), min, box_shape)
+    }
 }
 
 /// A constant shape of an `N`-dimensional array.
 pub trait ConstShape<const N: usize> {
-    type Coord;
+    type Coord: ShapeScalar;
 
     /// The number of elements in an array with this shape.
     const SIZE: Self::Coord;
@@ -123,10 +674,40 @@ pub trait ConstShape<const N: usize> {
     const USIZE: usize;
     /// The dimensions of the shape.
     const ARRAY: [Self::Coord; N];
+    /// The linear distance between neighboring elements along each axis.
+    const STRIDES: [Self::Coord; N];
     /// Translate an `N`-dimensional vector into a single number `T` that can be used for linear indexing.
     fn linearize(p: [Self::Coord; N]) -> Self::Coord;
     /// The inverse of `linearize`.
     fn delinearize(i: Self::Coord) -> [Self::Coord; N];
+
+    /// Reinterprets an array with this shape as having shape `S`, possibly with a different number of dimensions. Fails
+    /// to compile unless both shapes have the same number of elements.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape, ConstShape2u32, ConstShape3u32};
+    ///
+    /// let shape: ConstShape2u32<64, 256> = ConstShape3u32::<64, 64, 4>::reshape();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use ndshape::{ConstShape, ConstShape2u32, ConstShape3u32};
+    ///
+    /// let shape: ConstShape2u32<64, 255> = ConstShape3u32::<64, 64, 4>::reshape();
+    /// ```
+    #[inline]
+    fn reshape<S, const M: usize>() -> S
+    where
+        S: ConstShape<M> + Default,
+    {
+        const {
+            assert!(
+                Self::USIZE == S::USIZE,
+                "shapes must have the same number of elements"
+            )
+        };
+        S::default()
+    }
 }
 
 impl<S, const N: usize> AbstractShape<S::Coord, [S::Coord; N]> for S
@@ -166,6 +747,10 @@ where
         S::ARRAY
     }
     #[inline]
+    fn strides(&self) -> [Self::Coord; N] {
+        S::STRIDES
+    }
+    #[inline]
     fn linearize(&self, p: [Self::Coord; N]) -> Self::Coord {
         S::linearize(p)
     }
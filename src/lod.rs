@@ -0,0 +1,159 @@
+//! Level-of-detail hierarchies of shapes, where each level halves the dimensions of the one below it.
+//!
+//! ```
+//! use ndshape::{ConstPow2Shape3u32, LodShape, Shape};
+//!
+//! // Mip levels of a 16 x 16 x 16 chunk.
+//! let shape = ConstPow2Shape3u32::<4, 4, 4>;
+//! assert_eq!(shape.num_levels(), 5);
+//! assert_eq!(shape.downsample(1).as_array(), [8, 8, 8]);
+//! assert_eq!(shape.downsample(4).as_array(), [1, 1, 1]);
+//!
+//! let level1 = shape.downsample(1);
+//! let level2 = shape.downsample(2);
+//! let index = level1.linearize([3, 4, 5]);
+//! assert_eq!(shape.parent_index(1, index), level2.linearize([1, 2, 2]));
+//!
+//! let children = shape.child_indices(2, level2.linearize([1, 2, 2]));
+//! assert_eq!(children.len(), 8);
+//! assert!(children.contains(&index));
+//! ```
+
+use crate::{BoxIter, Shape, ShapeScalar};
+
+/// A shape that can be downsampled into a hierarchy of levels, where level 0 is the shape itself and each dimension of
+/// level `k + 1` is half of the one at level `k`, rounded up.
+///
+/// Power-of-two shapes downsample into power-of-two shapes, so mapping indices between their levels is only shifts and
+/// masks. Other shapes round odd dimensions up, so the last parent along such an axis has a single child.
+///
+/// ```
+/// use ndshape::{LodShape, RuntimeShape, Shape};
+///
+/// let shape = RuntimeShape::<u32, 2>::new([5, 3]);
+/// assert_eq!(shape.num_levels(), 4);
+/// assert_eq!(shape.downsample(1).as_array(), [3, 2]);
+/// assert_eq!(shape.downsample(2).as_array(), [2, 1]);
+///
+/// let level1 = shape.downsample(1);
+/// assert_eq!(shape.child_indices(1, level1.linearize([2, 1])), [shape.linearize([4, 2])]);
+/// assert_eq!(shape.parent_index(0, shape.linearize([4, 2])), level1.linearize([2, 1]));
+/// ```
+pub trait LodShape<const N: usize>: Shape<N> {
+    /// The shape of a downsampled level.
+    type Level: Shape<N, Coord = Self::Coord>;
+
+    /// The shape of `level`, where level 0 has the same dimensions as `self`.
+    fn downsample(&self, level: u32) -> Self::Level;
+
+    /// The number of levels, down to and including the first one with a single element.
+    fn num_levels(&self) -> u32 {
+        let mut level = 0;
+        while self
+            .downsample(level)
+            .as_array()
+            .iter()
+            .any(|&dim| dim > Self::Coord::ONE)
+        {
+            level += 1;
+        }
+        level + 1
+    }
+
+    /// The linear index at `level + 1` of the parent of the element at `index` in `level`.
+    fn parent_index(&self, level: u32, index: Self::Coord) -> Self::Coord {
+        let two = Self::Coord::ONE + Self::Coord::ONE;
+        let p = self.downsample(level).delinearize(index);
+        self.downsample(level + 1).linearize(p.map(|c| c / two))
+    }
+
+    /// The linear indices at `level - 1` of the children of the element at `index` in `level`, in increasing order.
+    ///
+    /// There are `2^N` children, except at the upper edges of odd dimensions. Panics if `level` is 0.
+    fn child_indices(&self, level: u32, index: Self::Coord) -> Vec<Self::Coord> {
+        assert!(level > 0, "level 0 has no children");
+        let two = Self::Coord::ONE + Self::Coord::ONE;
+        let p = self.downsample(level).delinearize(index);
+        let child_level = self.downsample(level - 1);
+        let child_dims = child_level.as_array();
+        let mut min = p;
+        let mut box_shape = p;
+        for i in 0..N {
+            min[i] = p[i] * two;
+            box_shape[i] = two.min(child_dims[i] - min[i]);
+        }
+        BoxIter::new(&child_level, min, box_shape)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// The dimensions of `level` for a shape with dimensions `array`, halving and rounding up once per level.
+pub(crate) fn downsample_dims<C: ShapeScalar, const N: usize>(array: [C; N], level: u32) -> [C; N] {
+    let two = C::ONE + C::ONE;
+    array.map(|mut dim| {
+        for _ in 0..level {
+            if dim <= C::ONE {
+                break;
+            }
+            dim = (dim + C::ONE) / two;
+        }
+        dim
+    })
+}
+
+/// The base-2 logarithms of the dimensions of `level` for a power-of-two shape with dimensions `1 << bits`.
+pub(crate) fn downsample_bits<C: ShapeScalar, const N: usize>(bits: [C; N], level: u32) -> [C; N] {
+    bits.map(|b| {
+        if b.to_usize() > level as usize {
+            b - C::from_usize(level as usize)
+        } else {
+            C::ZERO
+        }
+    })
+}
+
+/// [`LodShape::parent_index`] for a power-of-two shape with dimensions `1 << bits`, using only shifts and masks.
+#[inline]
+pub(crate) fn pow2_parent_index<C: ShapeScalar, const N: usize>(
+    bits: [C; N],
+    level: u32,
+    index: C,
+) -> C {
+    let from = downsample_bits(bits, level);
+    let to = downsample_bits(bits, level + 1);
+    let (mut shift, mut parent_shift, mut parent) = (C::ZERO, C::ZERO, C::ZERO);
+    for k in 0..N {
+        let c = (index >> shift) & !(!C::ZERO << from[k]);
+        parent = parent | ((c >> (from[k] - to[k])) << parent_shift);
+        shift = shift + from[k];
+        parent_shift = parent_shift + to[k];
+    }
+    parent
+}
+
+/// [`LodShape::child_indices`] for a power-of-two shape with dimensions `1 << bits`, using only shifts and masks.
+pub(crate) fn pow2_child_indices<C: ShapeScalar, const N: usize>(
+    bits: [C; N],
+    level: u32,
+    index: C,
+) -> Vec<C> {
+    assert!(level > 0, "level 0 has no children");
+    let from = downsample_bits(bits, level);
+    let to = downsample_bits(bits, level - 1);
+    let (mut shift, mut child_shift, mut first) = (C::ZERO, C::ZERO, C::ZERO);
+    let mut offsets = vec![C::ZERO];
+    for k in 0..N {
+        let c = (index >> shift) & !(!C::ZERO << from[k]);
+        first = first | ((c << (to[k] - from[k])) << child_shift);
+        if to[k] > from[k] {
+            // Each axis that was halved doubles the children, and its bit is above those of the previous axes.
+            let bit = C::ONE << child_shift;
+            let upper: Vec<C> = offsets.iter().map(|&o| o | bit).collect();
+            offsets.extend(upper);
+        }
+        shift = shift + from[k];
+        child_shift = child_shift + to[k];
+    }
+    offsets.into_iter().map(|o| first | o).collect()
+}
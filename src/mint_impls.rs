@@ -0,0 +1,71 @@
+//! [`AbstractShape`] implementations for [`mint`] vectors.
+//!
+//! Math libraries like `nalgebra` and `cgmath` convert to and from `mint` types, so this gives them all a path into
+//! [`Shape::linearize`] and [`Shape::delinearize`] without depending on each library.
+//!
+//! ```
+//! use mint::{Vector2, Vector3, Vector4};
+//! use ndshape::{AbstractShape, ConstPow2Shape4i64, ConstShape3u32, RuntimeShape};
+//!
+//! let shape = ConstShape3u32::<5, 6, 7>;
+//! let p = Vector3::from([1, 2, 3]);
+//! let index = AbstractShape::<u32, Vector3<u32>>::linearize(&shape, p);
+//! assert_eq!(index, 101);
+//! assert_eq!(AbstractShape::<u32, Vector3<u32>>::delinearize(&shape, index), p);
+//!
+//! let shape = RuntimeShape::<i16, 2>::new([5, 6]);
+//! let p = Vector2::from([4, 5]);
+//! let index = AbstractShape::<i16, Vector2<i16>>::linearize(&shape, p);
+//! assert_eq!(index, 29);
+//! assert_eq!(AbstractShape::<i16, Vector2<i16>>::delinearize(&shape, index), p);
+//!
+//! let shape = ConstPow2Shape4i64::<1, 2, 3, 4>;
+//! let p = Vector4::from([1, 2, 3, 4]);
+//! let index = AbstractShape::<i64, Vector4<i64>>::linearize(&shape, p);
+//! assert_eq!(AbstractShape::<i64, Vector4<i64>>::delinearize(&shape, index), p);
+//! ```
+
+use crate::{
+    AbstractShape, ConstPow2Shape2u8, ConstPow2Shape3usize, ConstPow2Shape4i16, ConstShape2i8,
+    ConstShape3u16, ConstShape4i64, RuntimePow2Shape, RuntimeShape, Shape,
+};
+
+use mint::{Vector2, Vector3, Vector4};
+use static_assertions::assert_impl_all;
+
+macro_rules! impl_mint_shape {
+    ($vector:ident, $n:literal) => {
+        impl<S, C> AbstractShape<C, $vector<C>> for S
+        where
+            S: Shape<$n, Coord = C>,
+        {
+            #[inline]
+            fn size(&self) -> C {
+                Shape::size(self)
+            }
+
+            #[inline]
+            fn linearize(&self, p: $vector<C>) -> C {
+                Shape::linearize(self, p.into())
+            }
+
+            #[inline]
+            fn delinearize(&self, i: C) -> $vector<C> {
+                Shape::delinearize(self, i).into()
+            }
+        }
+    };
+}
+
+impl_mint_shape!(Vector2, 2);
+impl_mint_shape!(Vector3, 3);
+impl_mint_shape!(Vector4, 4);
+
+assert_impl_all!(ConstShape2i8<1, 1>: AbstractShape<i8, Vector2<i8>>);
+assert_impl_all!(ConstShape3u16<1, 1, 1>: AbstractShape<u16, Vector3<u16>>);
+assert_impl_all!(ConstShape4i64<1, 1, 1, 1>: AbstractShape<i64, Vector4<i64>>);
+assert_impl_all!(ConstPow2Shape2u8<1, 1>: AbstractShape<u8, Vector2<u8>>);
+assert_impl_all!(ConstPow2Shape3usize<1, 1, 1>: AbstractShape<usize, Vector3<usize>>);
+assert_impl_all!(ConstPow2Shape4i16<1, 1, 1, 1>: AbstractShape<i16, Vector4<i16>>);
+assert_impl_all!(RuntimeShape<u32, 3>: AbstractShape<u32, Vector3<u32>>);
+assert_impl_all!(RuntimePow2Shape<i32, 3>: AbstractShape<i32, Vector3<i32>>);
@@ -0,0 +1,91 @@
+//! [`AbstractShape`] implementations for [`nalgebra`] fixed-size vectors and points.
+//!
+//! ```
+//! use nalgebra::{Point3, Vector2, Vector3};
+//! use ndshape::{AbstractShape, ConstShape3i32, RuntimePow2Shape, RuntimeShape, Shape};
+//!
+//! let shape = ConstShape3i32::<5, 6, 7>;
+//! let p = Point3::new(1, 2, 3);
+//! let index = AbstractShape::<i32, Point3<i32>>::linearize(&shape, p);
+//! assert_eq!(index, 101);
+//! assert_eq!(AbstractShape::<i32, Point3<i32>>::delinearize(&shape, index), p);
+//!
+//! let shape = RuntimeShape::from(Vector3::<u32>::new(5, 6, 7));
+//! assert_eq!(shape.as_array(), [5, 6, 7]);
+//! let p = Vector3::new(1, 2, 3);
+//! let index = AbstractShape::<u32, Vector3<u32>>::linearize(&shape, p);
+//! assert_eq!(index, 101);
+//! assert_eq!(AbstractShape::<u32, Vector3<u32>>::delinearize(&shape, index), p);
+//!
+//! let shape = RuntimePow2Shape::<u16, 2>::new([2, 3]);
+//! let p = Vector2::new(3, 7);
+//! let index = AbstractShape::<u16, Vector2<u16>>::linearize(&shape, p);
+//! assert_eq!(index, 0b111_11);
+//! assert_eq!(AbstractShape::<u16, Vector2<u16>>::delinearize(&shape, index), p);
+//! ```
+
+use crate::{
+    AbstractShape, ConstPow2Shape2u8, ConstPow2Shape4i64, ConstShape2u32, ConstShape3i32,
+    RuntimePow2Shape, RuntimeShape, Shape, ShapeScalar,
+};
+
+use nalgebra::{Point, SVector, Scalar, Vector2, Vector3, Vector4};
+use static_assertions::assert_impl_all;
+
+impl<S, C, const N: usize> AbstractShape<C, SVector<C, N>> for S
+where
+    S: Shape<N, Coord = C>,
+    C: Scalar,
+{
+    #[inline]
+    fn size(&self) -> C {
+        Shape::size(self)
+    }
+
+    #[inline]
+    fn linearize(&self, p: SVector<C, N>) -> C {
+        Shape::linearize(self, p.into())
+    }
+
+    #[inline]
+    fn delinearize(&self, i: C) -> SVector<C, N> {
+        Shape::delinearize(self, i).into()
+    }
+}
+
+impl<S, C, const N: usize> AbstractShape<C, Point<C, N>> for S
+where
+    S: Shape<N, Coord = C>,
+    C: Scalar,
+{
+    #[inline]
+    fn size(&self) -> C {
+        Shape::size(self)
+    }
+
+    #[inline]
+    fn linearize(&self, p: Point<C, N>) -> C {
+        Shape::linearize(self, p.into())
+    }
+
+    #[inline]
+    fn delinearize(&self, i: C) -> Point<C, N> {
+        Shape::delinearize(self, i).into()
+    }
+}
+
+impl<C, const N: usize> From<SVector<C, N>> for RuntimeShape<C, N>
+where
+    C: ShapeScalar + Scalar,
+{
+    fn from(dims: SVector<C, N>) -> Self {
+        Self::new(dims.into())
+    }
+}
+
+assert_impl_all!(ConstShape2u32<1, 1>: AbstractShape<u32, Vector2<u32>>);
+assert_impl_all!(ConstShape3i32<1, 1, 1>: AbstractShape<i32, Point<i32, 3>>);
+assert_impl_all!(ConstPow2Shape2u8<1, 1>: AbstractShape<u8, Point<u8, 2>>);
+assert_impl_all!(ConstPow2Shape4i64<1, 1, 1, 1>: AbstractShape<i64, Vector4<i64>>);
+assert_impl_all!(RuntimeShape<usize, 3>: AbstractShape<usize, Vector3<usize>>);
+assert_impl_all!(RuntimePow2Shape<i16, 4>: AbstractShape<i16, Point<i16, 4>>);
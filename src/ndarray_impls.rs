@@ -0,0 +1,261 @@
+//! Conversions between shapes and [`ndarray`] arrays.
+//!
+//! `ndarray` uses the C convention where the *last* axis is contiguous, while `ndshape` makes the *first* coordinate
+//! contiguous. So the axes are reversed when crossing over: the element at `shape.linearize([x, y, z])` is found at
+//! `view[[z, y, x]]`, and a row-major shape always maps to a standard-layout array.
+//!
+//! ```
+//! use ndshape::{array_view, array_view_mut, ConstShape3u32, RuntimeShape, Shape, ShapeError};
+//!
+//! let shape = ConstShape3u32::<5, 6, 7>;
+//! let mut data: Vec<u32> = (0..shape.size()).collect();
+//!
+//! let view = array_view(&shape, &data).unwrap();
+//! assert_eq!(view.shape(), &[7, 6, 5]);
+//! assert!(view.is_standard_layout());
+//! assert_eq!(view[[3, 2, 1]], shape.linearize([1, 2, 3]));
+//!
+//! // Convert back without copying.
+//! let runtime = RuntimeShape::<u32, 3>::try_from(&view).unwrap();
+//! assert_eq!(runtime.as_array(), shape.as_array());
+//!
+//! // Transposed arrays are not row-major.
+//! let transposed = RuntimeShape::<u32, 3>::try_from(&view.t());
+//! assert_eq!(transposed.err(), Some(ShapeError::IncompatibleLayout));
+//! assert_eq!(
+//!     array_view(&shape, &data[1..]).err(),
+//!     Some(ShapeError::LengthMismatch { expected: 210, actual: 209 })
+//! );
+//!
+//! let mut view = array_view_mut(&shape, &mut data).unwrap();
+//! view[[3, 2, 1]] = 0;
+//! assert_eq!(data[shape.linearize([1, 2, 3]) as usize], 0);
+//! ```
+//!
+//! Views keep the strides and origin of the shape they came from, so they borrow the same buffer.
+//!
+//! ```
+//! use ndshape::{array_view, ConstShape3u32, Flipped, Rotated, Shape, ShapeError, StridedShape};
+//!
+//! let shape = ConstShape3u32::<5, 6, 7>;
+//! let data: Vec<u32> = (0..shape.size()).collect();
+//!
+//! let plane = shape.slice_axis::<2>(2, 3);
+//! let view = array_view(&plane, &data).unwrap();
+//! assert_eq!(view.shape(), &[6, 5]);
+//! assert_eq!(view[[2, 1]], shape.linearize([1, 2, 3]));
+//!
+//! let half = shape.step_view([1, 0, 2], [2, 3, 2], [2, 2, 3]);
+//! let view = array_view(&half, &data).unwrap();
+//! assert_eq!(view[[2, 1, 1]], shape.linearize([3, 3, 6]));
+//!
+//! // Permuted axes, here the transpose of the first two.
+//! let transposed = StridedShape::new([6, 5, 7], [5, 1, 30], 0);
+//! let view = array_view(&transposed, &data).unwrap();
+//! assert_eq!(view[[3, 1, 2]], shape.linearize([1, 2, 3]));
+//!
+//! // Negative strides, e.g. of flipped or rotated shapes, give inverted axes.
+//! let flipped = Flipped::new(shape, [true, false, false]);
+//! let view = array_view(&flipped, &data).unwrap();
+//! assert_eq!(view[[3, 2, 1]], shape.linearize([3, 2, 3]));
+//!
+//! let rotated = Rotated::new(shape, [0, 2], 1);
+//! let view = array_view(&rotated, &data).unwrap();
+//! assert_eq!(view.shape(), &[5, 6, 7]);
+//! assert!(view
+//!     .indexed_iter()
+//!     .all(|((z, y, x), &i)| i == rotated.linearize([x, y, z].map(|c| c as u32))));
+//!
+//! // Zero strides alias elements, which `ndarray` views cannot do.
+//! let broadcast = ConstShape3u32::<5, 6, 1>.broadcast_to([5, 6, 7]).unwrap();
+//! assert_eq!(array_view(&broadcast, &data).err(), Some(ShapeError::IncompatibleLayout));
+//! assert_eq!(
+//!     array_view(&plane, &data[..100]).err(),
+//!     Some(ShapeError::LengthMismatch { expected: 120, actual: 100 })
+//! );
+//! ```
+
+use crate::scalar::{self, is_negative, magnitude};
+use crate::{RuntimeShape, Shape, ShapeError, ShapeScalar};
+
+use ndarray::{
+    ArrayBase, ArrayView, ArrayViewMut, Axis, Data, Dim, Dimension, IntoDimension, Ix, RawData,
+    ShapeBuilder, StrideShape,
+};
+
+/// The `ndarray` dimensions of `shape`, in reverse axis order.
+pub fn ndarray_dim<S, const N: usize>(shape: &S) -> Dim<[Ix; N]>
+where
+    S: Shape<N>,
+    [Ix; N]: IntoDimension<Dim = Dim<[Ix; N]>>,
+{
+    let mut dims = shape.as_array().map(ShapeScalar::to_usize);
+    dims.reverse();
+    dims.into_dimension()
+}
+
+/// The `ndarray` dimensions and strides of `shape`, in reverse axis order, along with which of those axes are inverted.
+/// The strides come from [`Shape::strides`], so views with explicit strides or permuted axes keep their layout.
+///
+/// `ndarray` layouts start at their lowest address, so the strides are the magnitudes of those of `shape`, and an axis
+/// with a negative stride is marked to be inverted with [`ArrayBase::invert_axis`] once the view is built.
+///
+/// Returns [`ShapeError::IncompatibleLayout`] if an axis with more than one element has a zero stride, which an
+/// `ndarray` view cannot describe, or [`ShapeError::Overflow`] if a stride does not fit in `usize`.
+#[allow(clippy::type_complexity)]
+pub fn ndarray_stride_shape<S, const N: usize>(
+    shape: &S,
+) -> Result<(StrideShape<Dim<[Ix; N]>>, [bool; N]), ShapeError>
+where
+    S: Shape<N>,
+    [Ix; N]: IntoDimension<Dim = Dim<[Ix; N]>>,
+    Dim<[Ix; N]>: Dimension,
+{
+    let dims = shape.as_array();
+    let mut strides = [0; N];
+    let mut inverted = [false; N];
+    for (k, &s) in shape.strides().iter().enumerate() {
+        if dims[k] <= S::Coord::ONE {
+            continue;
+        }
+        if s == S::Coord::ZERO {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        strides[k] = scalar::checked_to_usize(magnitude(s)).ok_or(ShapeError::Overflow)?;
+        inverted[k] = is_negative(s);
+    }
+    strides.reverse();
+    inverted.reverse();
+    Ok((
+        ndarray_dim(shape).strides(strides.into_dimension()),
+        inverted,
+    ))
+}
+
+/// The lowest linear index of `shape`, along with the length the buffer needs to reach every element.
+fn buffer_extent<S, const N: usize>(shape: &S) -> Result<(usize, usize), ShapeError>
+where
+    S: Shape<N>,
+{
+    if shape.is_empty() {
+        return Ok((0, 0));
+    }
+    let dims = shape.as_array();
+    let strides = shape.strides();
+    let mut first = shape.linearize([S::Coord::ZERO; N]);
+    for (&stride, &dim) in strides.iter().zip(dims.iter()) {
+        if is_negative(stride) && dim > S::Coord::ONE {
+            first = first.wrapping_add(stride.wrapping_mul(dim - S::Coord::ONE));
+        }
+    }
+    if is_negative(first) {
+        return Err(ShapeError::IncompatibleLayout);
+    }
+    let first = scalar::checked_to_usize(first).ok_or(ShapeError::Overflow)?;
+    let mut last = first;
+    for (&stride, &dim) in strides.iter().zip(dims.iter()) {
+        if dim > S::Coord::ONE {
+            let stride = scalar::checked_to_usize(magnitude(stride)).ok_or(ShapeError::Overflow)?;
+            last = scalar::checked_to_usize(dim - S::Coord::ONE)
+                .and_then(|steps| steps.checked_mul(stride))
+                .and_then(|span| span.checked_add(last))
+                .ok_or(ShapeError::Overflow)?;
+        }
+    }
+    Ok((first, last + 1))
+}
+
+/// Borrows `data` as an [`ArrayView`] with the layout of `shape`. Views like [`Shape::slice_axis`] or
+/// [`Shape::step_view`] borrow the buffer of the shape they came from, and axes with negative strides, like those of
+/// [`Flipped`](crate::Flipped) or [`Rotated`](crate::Rotated), are inverted.
+///
+/// Returns [`ShapeError::LengthMismatch`] if `data` is too short to hold the furthest element of `shape`, and otherwise
+/// the errors of [`ndarray_stride_shape`].
+pub fn array_view<'a, T, S, const N: usize>(
+    shape: &S,
+    data: &'a [T],
+) -> Result<ArrayView<'a, T, Dim<[Ix; N]>>, ShapeError>
+where
+    S: Shape<N>,
+    [Ix; N]: IntoDimension<Dim = Dim<[Ix; N]>>,
+    Dim<[Ix; N]>: Dimension,
+{
+    let (stride_shape, inverted) = ndarray_stride_shape(shape)?;
+    let first = check_extent(shape, data.len())?;
+    let mut view = ArrayView::from_shape(stride_shape, &data[first..])
+        .map_err(|_| ShapeError::IncompatibleLayout)?;
+    invert_axes(&mut view, inverted);
+    Ok(view)
+}
+
+/// Mutably borrows `data` as an [`ArrayViewMut`] with the layout of `shape`.
+///
+/// Returns the same errors as [`array_view`].
+pub fn array_view_mut<'a, T, S, const N: usize>(
+    shape: &S,
+    data: &'a mut [T],
+) -> Result<ArrayViewMut<'a, T, Dim<[Ix; N]>>, ShapeError>
+where
+    S: Shape<N>,
+    [Ix; N]: IntoDimension<Dim = Dim<[Ix; N]>>,
+    Dim<[Ix; N]>: Dimension,
+{
+    let (stride_shape, inverted) = ndarray_stride_shape(shape)?;
+    let first = check_extent(shape, data.len())?;
+    let mut view = ArrayViewMut::from_shape(stride_shape, &mut data[first..])
+        .map_err(|_| ShapeError::IncompatibleLayout)?;
+    invert_axes(&mut view, inverted);
+    Ok(view)
+}
+
+/// Returns the lowest linear index of `shape`, or [`ShapeError::LengthMismatch`] if a buffer of length `actual` does
+/// not reach every element.
+fn check_extent<S, const N: usize>(shape: &S, actual: usize) -> Result<usize, ShapeError>
+where
+    S: Shape<N>,
+{
+    let (first, expected) = buffer_extent(shape)?;
+    if actual < expected {
+        return Err(ShapeError::LengthMismatch { expected, actual });
+    }
+    Ok(first)
+}
+
+fn invert_axes<A: RawData, D: Dimension, const N: usize>(
+    array: &mut ArrayBase<A, D>,
+    inverted: [bool; N],
+) {
+    for (axis, inverted) in inverted.into_iter().enumerate() {
+        if inverted {
+            array.invert_axis(Axis(axis));
+        }
+    }
+}
+
+/// Only standard-layout arrays are accepted, and their axes are reversed to get the shape dimensions.
+impl<S, C, const N: usize> TryFrom<&ArrayBase<S, Dim<[Ix; N]>>> for RuntimeShape<C, N>
+where
+    S: Data,
+    C: ShapeScalar,
+    Dim<[Ix; N]>: Dimension,
+{
+    type Error = ShapeError;
+
+    fn try_from(array: &ArrayBase<S, Dim<[Ix; N]>>) -> Result<Self, ShapeError> {
+        if !array.is_standard_layout() {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        let mut dims = [C::ZERO; N];
+        for (dim, &len) in dims.iter_mut().zip(array.shape().iter().rev()) {
+            *dim = scalar::checked_from_usize(len).ok_or(ShapeError::Overflow)?;
+        }
+        if dims
+            .iter()
+            .try_fold(C::ONE, |acc, &d| acc.checked_mul(d))
+            .is_none()
+        {
+            return Err(ShapeError::Overflow);
+        }
+        Ok(Self::new(dims))
+    }
+}
@@ -0,0 +1,78 @@
+//! Parallel iteration over shapes with [`rayon`].
+//!
+//! ```
+//! use ndshape::{ConstShape3u32, ParallelShape, Shape};
+//! use rayon::prelude::*;
+//!
+//! let shape = ConstShape3u32::<5, 6, 7>;
+//!
+//! let items: Vec<_> = shape.par_iter_coords().collect();
+//! assert_eq!(items.len(), shape.usize());
+//! for (i, &(index, p)) in items.iter().enumerate() {
+//!     assert_eq!(index, i as u32);
+//!     assert_eq!(p, shape.delinearize(index));
+//! }
+//!
+//! let sum: u32 = shape.par_iter_box([1, 1, 1], [2, 2, 2]).map(|(index, _)| index).sum();
+//! assert_eq!(sum, 8 * shape.linearize([1, 1, 1]) + 4 * (1 + 5 + 30));
+//!
+//! let slabs: Vec<Vec<_>> = shape.par_chunks_by_slab(1).map(|slab| slab.collect()).collect();
+//! assert_eq!(slabs.len(), 6);
+//! assert!(slabs[2].iter().all(|&(_, p)| p[1] == 2));
+//! ```
+
+use crate::{BoxIter, Shape, ShapeScalar};
+
+use rayon::prelude::*;
+
+/// Parallel iterators over the points of a [`Shape`], yielding the linear index of each point along with its coordinates.
+///
+/// Work is split into whole rows or slabs, so each task touches contiguous memory, and only coordinates are stepped
+/// within a task, so there is no division per point.
+pub trait ParallelShape<const N: usize>: Shape<N> + Sync {
+    /// All points of the shape.
+    fn par_iter_coords(&self) -> impl ParallelIterator<Item = (Self::Coord, [Self::Coord; N])> {
+        self.par_iter_box([Self::Coord::ZERO; N], self.as_array())
+    }
+
+    /// All points of the box starting at `min` with dimensions `box_shape`, split into rows along axis 0.
+    fn par_iter_box(
+        &self,
+        min: [Self::Coord; N],
+        box_shape: [Self::Coord; N],
+    ) -> impl ParallelIterator<Item = (Self::Coord, [Self::Coord; N])> {
+        let dims = box_shape.map(|d| d.max(Self::Coord::ZERO).to_usize());
+        let num_rows = if N == 0 {
+            0
+        } else {
+            dims[1..].iter().product()
+        };
+        (0..num_rows).into_par_iter().flat_map_iter(move |mut row| {
+            let mut row_min = min;
+            let mut row_shape = box_shape;
+            for i in 1..N {
+                row_min[i] = min[i] + Self::Coord::from_usize(row % dims[i]);
+                row_shape[i] = Self::Coord::ONE;
+                row /= dims[i];
+            }
+            BoxIter::new(self, row_min, row_shape)
+        })
+    }
+
+    /// One sequential iterator per slab perpendicular to `axis`, in order of the coordinate along `axis`.
+    fn par_chunks_by_slab(
+        &self,
+        axis: usize,
+    ) -> impl IndexedParallelIterator<Item = BoxIter<'_, Self, Self::Coord, N>> {
+        let shape = self.as_array();
+        (0..shape[axis].to_usize()).into_par_iter().map(move |k| {
+            let mut min = [Self::Coord::ZERO; N];
+            let mut slab_shape = shape;
+            min[axis] = Self::Coord::from_usize(k);
+            slab_shape[axis] = Self::Coord::ONE;
+            BoxIter::new(self, min, slab_shape)
+        })
+    }
+}
+
+impl<S: Shape<N> + Sync, const N: usize> ParallelShape<N> for S {}
@@ -1,189 +1,204 @@
-use crate::Shape;
-
-#[derive(Clone)]
+use crate::{lod, strided_shape, LodShape, Shape, ShapeError, ShapeScalar};
+
+use core::num::NonZero;
+
+/// The row-major shape of an `N`-dimensional array whose dimensions are only known at runtime.
+///
+/// The same buffer can be reinterpreted under different shapes, as long as the number of elements stays the same.
+///
+/// ```
+/// use ndshape::{RuntimeShape, Shape, ShapeError};
+///
+/// let shape = RuntimeShape::<u32, 3>::new([64, 64, 4]);
+/// let flat = shape.reshape([64, 256]).unwrap();
+/// assert_eq!(flat.as_array(), [64, 256]);
+/// assert_eq!(shape.reshape([64, 255]).err(), Some(ShapeError::SizeMismatch));
+/// assert_eq!(shape.reshape([1 << 24, 257]).err(), Some(ShapeError::Overflow));
+///
+/// let volume = RuntimeShape::<u32, 3>::new([5, 1, 7]);
+/// let plane = volume.squeeze_axis(1).unwrap();
+/// assert_eq!(plane, RuntimeShape::<u32, 2>::new([5, 7]));
+/// assert_eq!(plane.insert_axis(1), volume);
+/// assert_eq!(
+///     volume.squeeze_axis::<2>(0),
+///     Err(ShapeError::NotSingletonAxis { axis: 0 })
+/// );
+/// ```
+///
+/// Dimensions may be zero, e.g. for the intersection of two disjoint extents, which gives an empty shape.
+///
+/// ```
+/// use core::num::NonZero;
+/// use ndshape::{RuntimeShape, Shape};
+///
+/// let empty = RuntimeShape::<u32, 3>::new([0, 5, 5]);
+/// assert!(empty.is_empty());
+/// assert_eq!(empty.try_delinearize(0), None);
+/// assert_eq!(empty.iter_boundary().count(), 0);
+///
+/// let dims = [5, 6, 7].map(|d| NonZero::new(d).unwrap());
+/// let shape = RuntimeShape::<u32, 3>::new_nonzero(dims);
+/// assert!(!shape.is_empty());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RuntimeShape<C, const N: usize> {
     array: [C; N],
     strides: [C; N],
     size: C,
 }
 
-macro_rules! impl_shape2 {
-    ($scalar:ident) => {
-        impl RuntimeShape<$scalar, 2> {
-            pub fn new([x, y]: [$scalar; 2]) -> Self {
-                Self {
-                    array: [x, y],
-                    strides: [1, x],
-                    size: x * y,
-                }
-            }
+impl<C: ShapeScalar, const N: usize> RuntimeShape<C, N> {
+    pub fn new(array: [C; N]) -> Self {
+        Self {
+            array,
+            strides: strided_shape::row_major_strides(array),
+            size: array.iter().fold(C::ONE, |size, &dim| size * dim),
         }
-
-        impl Shape<2> for RuntimeShape<$scalar, 2> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 2] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
-
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
-
-            #[inline]
-            fn linearize(&self, p: [$scalar; 2]) -> $scalar {
-                p[0] + self.strides[1].wrapping_mul(p[1])
-            }
-
-            #[inline]
-            fn delinearize(&self, i: $scalar) -> [$scalar; 2] {
-                let y = i / self.strides[1];
-                let x = i % self.strides[1];
-                [x, y]
-            }
+    }
+
+    /// The same number of elements with a different shape and possibly a different number of dimensions.
+    ///
+    /// Returns [`ShapeError::SizeMismatch`] if `array` does not have the same number of elements as `self`, or
+    /// [`ShapeError::Overflow`] if its number of elements does not fit in `C`.
+    pub fn reshape<const M: usize>(&self, array: [C; M]) -> Result<RuntimeShape<C, M>, ShapeError> {
+        let size = array
+            .iter()
+            .try_fold(C::ONE, |size, &dim| size.checked_mul(dim))
+            .ok_or(ShapeError::Overflow)?;
+        if size != self.size {
+            return Err(ShapeError::SizeMismatch);
         }
-    };
-}
-
-impl_shape2!(u8);
-impl_shape2!(u16);
-impl_shape2!(u32);
-impl_shape2!(u64);
-impl_shape2!(usize);
-
-impl_shape2!(i8);
-impl_shape2!(i16);
-impl_shape2!(i32);
-impl_shape2!(i64);
-
-macro_rules! impl_shape3 {
-    ($scalar:ident) => {
-        impl RuntimeShape<$scalar, 3> {
-            pub fn new([x, y, z]: [$scalar; 3]) -> Self {
-                Self {
-                    array: [x, y, z],
-                    strides: [1, x, x * y],
-                    size: x * y * z,
-                }
-            }
+        Ok(RuntimeShape::new(array))
+    }
+
+    /// Removes `axis`, which must have a dimension of 1. `M` must equal `N - 1`.
+    ///
+    /// Returns [`ShapeError::NotSingletonAxis`] if the dimension of `axis` is not 1.
+    pub fn squeeze_axis<const M: usize>(
+        &self,
+        axis: usize,
+    ) -> Result<RuntimeShape<C, M>, ShapeError> {
+        const { assert!(M + 1 == N, "squeezing must remove exactly one axis") };
+        assert!(axis < N, "axis {axis} is out of bounds for {N} dimensions");
+        if self.array[axis] != C::ONE {
+            return Err(ShapeError::NotSingletonAxis { axis });
         }
+        let mut array = [C::ZERO; M];
+        for (i, dim) in array.iter_mut().enumerate() {
+            *dim = self.array[if i < axis { i } else { i + 1 }];
+        }
+        Ok(RuntimeShape::new(array))
+    }
+
+    /// Inserts a new `axis` with a dimension of 1. `M` must equal `N + 1`.
+    pub fn insert_axis<const M: usize>(&self, axis: usize) -> RuntimeShape<C, M> {
+        const { assert!(M == N + 1, "inserting must add exactly one axis") };
+        assert!(axis <= N, "axis {axis} is out of bounds for {N} dimensions");
+        let mut array = [C::ONE; M];
+        for (i, &dim) in self.array.iter().enumerate() {
+            array[if i < axis { i } else { i + 1 }] = dim;
+        }
+        RuntimeShape::new(array)
+    }
+}
 
-        impl Shape<3> for RuntimeShape<$scalar, 3> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 3] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
-
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
-
-            #[inline]
-            fn linearize(&self, p: [$scalar; 3]) -> $scalar {
-                p[0] + self.strides[1].wrapping_mul(p[1]) + self.strides[2].wrapping_mul(p[2])
-            }
-
-            #[inline]
-            fn delinearize(&self, mut i: $scalar) -> [$scalar; 3] {
-                let z = i / self.strides[2];
-                i -= z * self.strides[2];
-                let y = i / self.strides[1];
-                let x = i % self.strides[1];
-                [x, y, z]
+macro_rules! impl_new_nonzero {
+    ($scalar:ty) => {
+        impl<const N: usize> RuntimeShape<$scalar, N> {
+            /// Like [`Self::new`], but the dimensions are guaranteed to be nonzero, so the shape is never empty.
+            pub fn new_nonzero(array: [NonZero<$scalar>; N]) -> Self {
+                Self::new(array.map(NonZero::get))
             }
         }
     };
 }
 
-impl_shape3!(u8);
-impl_shape3!(u16);
-impl_shape3!(u32);
-impl_shape3!(u64);
-impl_shape3!(usize);
-
-impl_shape3!(i8);
-impl_shape3!(i16);
-impl_shape3!(i32);
-impl_shape3!(i64);
-
-macro_rules! impl_shape4 {
-    ($scalar:ident) => {
-        impl RuntimeShape<$scalar, 4> {
-            pub fn new([x, y, z, w]: [$scalar; 4]) -> Self {
-                Self {
-                    array: [x, y, z, w],
-                    strides: [1, x, x * y, x * y * z],
-                    size: x * y * z * w,
-                }
-            }
+impl_new_nonzero!(u8);
+impl_new_nonzero!(u16);
+impl_new_nonzero!(u32);
+impl_new_nonzero!(u64);
+impl_new_nonzero!(u128);
+impl_new_nonzero!(usize);
+
+impl_new_nonzero!(i8);
+impl_new_nonzero!(i16);
+impl_new_nonzero!(i32);
+impl_new_nonzero!(i64);
+impl_new_nonzero!(i128);
+
+impl<C: ShapeScalar, const N: usize> Shape<N> for RuntimeShape<C, N> {
+    type Coord = C;
+
+    #[inline]
+    fn as_array(&self) -> [C; N] {
+        self.array
+    }
+
+    #[inline]
+    fn strides(&self) -> [C; N] {
+        self.strides
+    }
+
+    #[inline]
+    fn size(&self) -> C {
+        self.size
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.size.to_usize()
+    }
+
+    #[inline]
+    fn linearize(&self, p: [C; N]) -> C {
+        self.strides.iter().zip(p).fold(C::ZERO, |i, (&stride, c)| {
+            i.wrapping_add(stride.wrapping_mul(c))
+        })
+    }
+
+    #[inline]
+    fn delinearize(&self, mut i: C) -> [C; N] {
+        let mut p = [C::ZERO; N];
+        if self.size == C::ZERO {
+            return p;
         }
-
-        impl Shape<4> for RuntimeShape<$scalar, 4> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 4] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
-
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
-
-            #[inline]
-            fn linearize(&self, p: [$scalar; 4]) -> $scalar {
-                p[0] + self.strides[1].wrapping_mul(p[1])
-                    + self.strides[2].wrapping_mul(p[2])
-                    + self.strides[3].wrapping_mul(p[3])
-            }
-
-            #[inline]
-            fn delinearize(&self, mut i: $scalar) -> [$scalar; 4] {
-                let w = i / self.strides[3];
-                i -= w * self.strides[3];
-                let z = i / self.strides[2];
-                i -= z * self.strides[2];
-                let y = i / self.strides[1];
-                let x = i % self.strides[1];
-                [x, y, z, w]
-            }
+        for k in (1..N).rev() {
+            p[k] = i / self.strides[k];
+            i = i - p[k] * self.strides[k];
         }
-    };
+        if N > 0 {
+            p[0] = i;
+        }
+        p
+    }
 }
 
-impl_shape4!(u8);
-impl_shape4!(u16);
-impl_shape4!(u32);
-impl_shape4!(u64);
-impl_shape4!(usize);
+impl<C: ShapeScalar, const N: usize> LodShape<N> for RuntimeShape<C, N> {
+    type Level = Self;
 
-impl_shape4!(i8);
-impl_shape4!(i16);
-impl_shape4!(i32);
-impl_shape4!(i64);
+    #[inline]
+    fn downsample(&self, level: u32) -> Self {
+        Self::new(lod::downsample_dims(self.array, level))
+    }
+}
 
-#[derive(Clone)]
+/// The shape of an `N`-dimensional array whose dimensions are powers of two only known at runtime.
+///
+/// ```
+/// use ndshape::{RuntimePow2Shape, Shape};
+///
+/// let shape = RuntimePow2Shape::<u32, 3>::new([6, 6, 2]);
+/// let flat = shape.reshape([6, 8]).unwrap();
+/// assert_eq!(flat.as_array(), [64, 256]);
+/// assert!(shape.reshape([6, 7]).is_err());
+/// assert_eq!(flat.insert_axis::<3>(2).squeeze_axis(2), Ok(flat));
+///
+/// // Every axis contributes to the index, including the fourth.
+/// let shape = RuntimePow2Shape::<u32, 4>::new([1, 2, 3, 4]);
+/// assert_eq!(shape.linearize([0, 0, 0, 1]), 1 << 6);
+/// assert_eq!(shape.delinearize(1 << 6), [0, 0, 0, 1]);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RuntimePow2Shape<C, const N: usize> {
     array: [C; N],
     shifts: [C; N],
@@ -191,187 +206,136 @@ pub struct RuntimePow2Shape<C, const N: usize> {
     size: C,
 }
 
-macro_rules! impl_pow2_shape2 {
-    ($scalar:ty) => {
-        impl RuntimePow2Shape<$scalar, 2> {
-            pub fn new([x, y]: [$scalar; 2]) -> Self {
-                let y_shift = x;
-                Self {
-                    array: [1 << x, 1 << y],
-                    shifts: [0, y_shift],
-                    size: 1 << x + y,
-                    masks: [!(!0 << x), !(!0 << y) << y_shift],
-                }
-            }
+impl<C: ShapeScalar, const N: usize> RuntimePow2Shape<C, N> {
+    /// `bits` are the base-2 logarithms of the dimensions.
+    pub fn new(bits: [C; N]) -> Self {
+        let mut array = [C::ZERO; N];
+        let mut shifts = [C::ZERO; N];
+        let mut masks = [C::ZERO; N];
+        let mut shift = C::ZERO;
+        for i in 0..N {
+            array[i] = C::ONE << bits[i];
+            shifts[i] = shift;
+            masks[i] = !(!C::ZERO << bits[i]) << shift;
+            shift = shift + bits[i];
         }
-
-        impl Shape<2> for RuntimePow2Shape<$scalar, 2> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 2] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
-
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
-
-            #[inline]
-            fn linearize(&self, p: [$scalar; 2]) -> $scalar {
-                (p[1] << self.shifts[1]) | p[0]
-            }
-
-            #[inline]
-            fn delinearize(&self, i: $scalar) -> [$scalar; 2] {
-                [i & self.masks[0], (i & self.masks[1]) >> self.shifts[1]]
-            }
+        Self {
+            array,
+            shifts,
+            masks,
+            size: C::ONE << shift,
         }
-    };
-}
-
-impl_pow2_shape2!(u8);
-impl_pow2_shape2!(u16);
-impl_pow2_shape2!(u32);
-impl_pow2_shape2!(u64);
-impl_pow2_shape2!(usize);
-
-impl_pow2_shape2!(i8);
-impl_pow2_shape2!(i16);
-impl_pow2_shape2!(i32);
-impl_pow2_shape2!(i64);
-
-macro_rules! impl_pow2_shape3 {
-    ($scalar:ty) => {
-        impl RuntimePow2Shape<$scalar, 3> {
-            pub fn new([x, y, z]: [$scalar; 3]) -> Self {
-                let y_shift = x;
-                let z_shift = x + y;
-                Self {
-                    array: [1 << x, 1 << y, 1 << z],
-                    shifts: [0, y_shift, z_shift],
-                    masks: [!(!0 << x), !(!0 << y) << y_shift, !(!0 << z) << z_shift],
-                    size: 1 << x + y + z,
-                }
-            }
+    }
+
+    /// The base-2 logarithms of the dimensions.
+    #[inline]
+    pub fn bits(&self) -> [C; N] {
+        self.array
+            .map(|dim| C::from_usize(dim.trailing_zeros() as usize))
+    }
+
+    /// The same number of elements with a different shape and possibly a different number of dimensions. Like
+    /// [`Self::new`], the shape is given as the base-2 logarithms of the dimensions.
+    ///
+    /// Returns [`ShapeError::SizeMismatch`] if `bits` does not have the same number of elements as `self`.
+    pub fn reshape<const M: usize>(
+        &self,
+        bits: [C; M],
+    ) -> Result<RuntimePow2Shape<C, M>, ShapeError> {
+        let total_bits = |bits: &[C]| bits.iter().fold(C::ZERO, |sum, &b| sum + b);
+        if total_bits(&bits) != total_bits(&self.bits()) {
+            return Err(ShapeError::SizeMismatch);
         }
-
-        impl Shape<3> for RuntimePow2Shape<$scalar, 3> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 3] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
-
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
-
-            #[inline]
-            fn linearize(&self, p: [$scalar; 3]) -> $scalar {
-                (p[2] << self.shifts[2]) | (p[1] << self.shifts[1]) | p[0]
-            }
-
-            #[inline]
-            fn delinearize(&self, i: $scalar) -> [$scalar; 3] {
-                [
-                    i & self.masks[0],
-                    (i & self.masks[1]) >> self.shifts[1],
-                    (i & self.masks[2]) >> self.shifts[2],
-                ]
-            }
+        Ok(RuntimePow2Shape::new(bits))
+    }
+
+    /// Removes `axis`, which must have a dimension of 1. `M` must equal `N - 1`.
+    ///
+    /// Returns [`ShapeError::NotSingletonAxis`] if the dimension of `axis` is not 1.
+    pub fn squeeze_axis<const M: usize>(
+        &self,
+        axis: usize,
+    ) -> Result<RuntimePow2Shape<C, M>, ShapeError> {
+        const { assert!(M + 1 == N, "squeezing must remove exactly one axis") };
+        assert!(axis < N, "axis {axis} is out of bounds for {N} dimensions");
+        if self.array[axis] != C::ONE {
+            return Err(ShapeError::NotSingletonAxis { axis });
         }
-    };
+        let bits = self.bits();
+        let mut new_bits = [C::ZERO; M];
+        for (i, b) in new_bits.iter_mut().enumerate() {
+            *b = bits[if i < axis { i } else { i + 1 }];
+        }
+        Ok(RuntimePow2Shape::new(new_bits))
+    }
+
+    /// Inserts a new `axis` with a dimension of 1. `M` must equal `N + 1`.
+    pub fn insert_axis<const M: usize>(&self, axis: usize) -> RuntimePow2Shape<C, M> {
+        const { assert!(M == N + 1, "inserting must add exactly one axis") };
+        assert!(axis <= N, "axis {axis} is out of bounds for {N} dimensions");
+        let mut new_bits = [C::ZERO; M];
+        for (i, b) in self.bits().into_iter().enumerate() {
+            new_bits[if i < axis { i } else { i + 1 }] = b;
+        }
+        RuntimePow2Shape::new(new_bits)
+    }
 }
 
-impl_pow2_shape3!(u8);
-impl_pow2_shape3!(u16);
-impl_pow2_shape3!(u32);
-impl_pow2_shape3!(u64);
-impl_pow2_shape3!(usize);
-
-impl_pow2_shape3!(i8);
-impl_pow2_shape3!(i16);
-impl_pow2_shape3!(i32);
-impl_pow2_shape3!(i64);
-
-macro_rules! impl_pow2_shape4 {
-    ($scalar:ty) => {
-        impl RuntimePow2Shape<$scalar, 4> {
-            pub fn new([x, y, z, w]: [$scalar; 4]) -> Self {
-                let y_shift = x;
-                let z_shift = x + y;
-                let w_shift = x + y + z;
-                Self {
-                    array: [1 << x, 1 << y, 1 << z, 1 << w],
-                    size: 1 << (x + y + z + w),
-                    shifts: [0, y_shift, z_shift, w_shift],
-                    masks: [
-                        !(!0 << x),
-                        !(!0 << y) << y_shift,
-                        !(!0 << z) << z_shift,
-                        !(!0 << w) << w_shift,
-                    ],
-                }
-            }
+impl<C: ShapeScalar, const N: usize> Shape<N> for RuntimePow2Shape<C, N> {
+    type Coord = C;
+
+    #[inline]
+    fn as_array(&self) -> [C; N] {
+        self.array
+    }
+
+    #[inline]
+    fn strides(&self) -> [C; N] {
+        self.shifts.map(|shift| C::ONE << shift)
+    }
+
+    #[inline]
+    fn size(&self) -> C {
+        self.size
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.size.to_usize()
+    }
+
+    #[inline]
+    fn linearize(&self, p: [C; N]) -> C {
+        self.shifts
+            .iter()
+            .zip(p)
+            .fold(C::ZERO, |i, (&shift, c)| i | (c << shift))
+    }
+
+    #[inline]
+    fn delinearize(&self, i: C) -> [C; N] {
+        let mut p = [C::ZERO; N];
+        for (k, c) in p.iter_mut().enumerate() {
+            *c = (i & self.masks[k]) >> self.shifts[k];
         }
+        p
+    }
+}
 
-        impl Shape<4> for RuntimePow2Shape<$scalar, 4> {
-            type Coord = $scalar;
-
-            #[inline]
-            fn as_array(&self) -> [$scalar; 4] {
-                self.array
-            }
-
-            #[inline]
-            fn size(&self) -> $scalar {
-                self.size
-            }
+impl<C: ShapeScalar, const N: usize> LodShape<N> for RuntimePow2Shape<C, N> {
+    type Level = Self;
 
-            #[inline]
-            fn usize(&self) -> usize {
-                self.size as usize
-            }
+    #[inline]
+    fn downsample(&self, level: u32) -> Self {
+        Self::new(lod::downsample_bits(self.bits(), level))
+    }
 
-            #[inline]
-            fn linearize(&self, p: [$scalar; 4]) -> $scalar {
-                (p[2] << self.shifts[2]) | (p[1] << self.shifts[1]) | p[0]
-            }
+    #[inline]
+    fn parent_index(&self, level: u32, index: C) -> C {
+        lod::pow2_parent_index(self.bits(), level, index)
+    }
 
-            #[inline]
-            fn delinearize(&self, i: $scalar) -> [$scalar; 4] {
-                [
-                    i & self.masks[0],
-                    (i & self.masks[1]) >> self.shifts[1],
-                    (i & self.masks[2]) >> self.shifts[2],
-                    (i & self.masks[3]) >> self.shifts[3],
-                ]
-            }
-        }
-    };
+    fn child_indices(&self, level: u32, index: C) -> Vec<C> {
+        lod::pow2_child_indices(self.bits(), level, index)
+    }
 }
-
-impl_pow2_shape4!(u8);
-impl_pow2_shape4!(u16);
-impl_pow2_shape4!(u32);
-impl_pow2_shape4!(u64);
-impl_pow2_shape4!(usize);
-
-impl_pow2_shape4!(i8);
-impl_pow2_shape4!(i16);
-impl_pow2_shape4!(i32);
-impl_pow2_shape4!(i64);
@@ -0,0 +1,148 @@
+use core::fmt::Debug;
+use core::ops::{Add, BitAnd, BitOr, Div, Mul, Not, Rem, Shl, Shr, Sub};
+
+/// The integer arithmetic, shifts and masks needed by shapes and by algorithms that are generic over a shape's
+/// coordinate type.
+///
+/// Implemented for all of the primitive integers. It can also be implemented for integer newtypes, which makes them
+/// usable as coordinates of [`RuntimeShape`](crate::RuntimeShape), [`RuntimePow2Shape`](crate::RuntimePow2Shape) and
+/// any other shape that is generic over its coordinate type.
+///
+/// ```
+/// use ndshape::{ConstShape3u8, RuntimeShape, Shape, ShapeScalar};
+///
+/// fn center_index<C: ShapeScalar, const N: usize>(shape: &impl Shape<N, Coord = C>) -> C {
+///     let two = C::ONE + C::ONE;
+///     shape.linearize(shape.as_array().map(|dim| dim / two))
+/// }
+///
+/// assert_eq!(center_index(&ConstShape3u8::<4, 4, 4>), 2 + 4 * 2 + 16 * 2);
+/// assert_eq!(center_index(&RuntimeShape::<i64, 2>::new([10, 10])), 55);
+/// ```
+pub trait ShapeScalar:
+    Copy
+    + Debug
+    + Send
+    + Sync
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Shl<Self, Output = Self>
+    + Shr<Self, Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn trailing_zeros(self) -> u32;
+
+    /// Same as `i as Self`.
+    fn from_usize(i: usize) -> Self;
+    /// Same as `self as usize`.
+    fn to_usize(self) -> usize;
+}
+
+/// Whether `c` is negative, reading unsigned values as two's complement like the wrapped negative strides of flipped
+/// axes.
+#[inline]
+pub(crate) fn is_negative<C: ShapeScalar>(c: C) -> bool {
+    let is_signed = !C::ZERO < C::ZERO;
+    if is_signed {
+        c < C::ZERO
+    } else {
+        c > !C::ZERO >> C::ONE
+    }
+}
+
+/// The absolute value of `c`, reading unsigned values as two's complement.
+#[inline]
+pub(crate) fn magnitude<C: ShapeScalar>(c: C) -> C {
+    if is_negative(c) {
+        C::ZERO.wrapping_sub(c)
+    } else {
+        c
+    }
+}
+
+/// `c` as a `usize`, or `None` if it is negative or does not fit.
+#[cfg(feature = "ndarray")]
+#[inline]
+pub(crate) fn checked_to_usize<C: ShapeScalar>(c: C) -> Option<usize> {
+    let i = c.to_usize();
+    (C::ZERO <= c && C::from_usize(i) == c).then_some(i)
+}
+
+/// `i` as a `C`, or `None` if it does not fit.
+#[cfg(feature = "ndarray")]
+#[inline]
+pub(crate) fn checked_from_usize<C: ShapeScalar>(i: usize) -> Option<C> {
+    let c = C::from_usize(i);
+    (C::ZERO <= c && c.to_usize() == i).then_some(c)
+}
+
+macro_rules! impl_shape_scalar {
+    ($scalar:ty) => {
+        impl ShapeScalar for $scalar {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                self.wrapping_add(rhs)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                self.wrapping_sub(rhs)
+            }
+
+            #[inline]
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                self.wrapping_mul(rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.checked_mul(rhs)
+            }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                self.trailing_zeros()
+            }
+
+            #[inline]
+            fn from_usize(i: usize) -> Self {
+                i as Self
+            }
+
+            #[inline]
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+        }
+    };
+}
+
+impl_shape_scalar!(u8);
+impl_shape_scalar!(u16);
+impl_shape_scalar!(u32);
+impl_shape_scalar!(u64);
+impl_shape_scalar!(u128);
+impl_shape_scalar!(usize);
+
+impl_shape_scalar!(i8);
+impl_shape_scalar!(i16);
+impl_shape_scalar!(i32);
+impl_shape_scalar!(i64);
+impl_shape_scalar!(i128);
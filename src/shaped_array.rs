@@ -0,0 +1,444 @@
+//! Containers that pair a buffer with the [`Shape`] used to index it.
+//!
+//! ```
+//! use ndshape::{ConstShape3u32, ConstShapedArray, RuntimeShape, ShapedArray, ShapedSlice};
+//!
+//! let shape = RuntimeShape::<u32, 3>::new([5, 6, 7]);
+//! let mut array = ShapedArray::new(shape, vec![0u8; 5 * 6 * 7]).unwrap();
+//! array[[1, 2, 3]] = 1;
+//! assert_eq!(array.as_slice()[101], 1);
+//! assert_eq!(array.get([4, 5, 6]), Some(&0));
+//! assert_eq!(array.get([5, 0, 0]), None);
+//!
+//! for (p, value) in array.indexed_iter_mut() {
+//!     *value = p[0] as u8;
+//! }
+//! assert_eq!(array[[4, 2, 3]], 4);
+//!
+//! let doubled = array.map(|&v| 2 * v);
+//! assert_eq!(doubled[[4, 2, 3]], 8);
+//!
+//! let data = [7; 6];
+//! let slice = ShapedSlice::new(ConstShape3u32::<1, 2, 3>, &data[..]).unwrap();
+//! assert_eq!(slice[[0, 1, 2]], 7);
+//! assert!(ShapedSlice::new(ConstShape3u32::<2, 2, 2>, &data[..]).is_err());
+//!
+//! let mut stack = ConstShapedArray::<u8, ConstShape3u32<2, 2, 2>, 8>::new([0; 8]);
+//! stack[[1, 1, 1]] = 9;
+//! assert_eq!(stack.as_slice()[7], 9);
+//! ```
+//!
+//! The shape must index exactly the whole buffer. Flipped and rotated shapes do, but views into a larger shape do not.
+//!
+//! ```
+//! use ndshape::{ConstShape3u32, Flipped, Shape, ShapedArray, ShapeError, Window};
+//!
+//! let shape = ConstShape3u32::<4, 4, 4>;
+//! let data: Vec<u32> = (0..shape.size()).collect();
+//!
+//! let flipped = ShapedArray::new(Flipped::new(shape, [true, false, true]), data.clone()).unwrap();
+//! assert_eq!(flipped[[0, 0, 0]], shape.linearize([3, 0, 3]));
+//! assert!(flipped
+//!     .indexed_iter()
+//!     .all(|(p, &i)| i == flipped.shape().linearize(p)));
+//!
+//! let window = Window::new(ConstShape3u32::<6, 6, 6>, [1, 1, 1], [4, 4, 4]);
+//! assert_eq!(ShapedArray::new(window, data.clone()).err(), Some(ShapeError::IncompatibleLayout));
+//!
+//! let plane = ConstShape3u32::<5, 6, 1>.slice_axis::<2>(2, 0);
+//! assert!(ShapedArray::new(plane, vec![0; 30]).is_ok());
+//! let plane = ConstShape3u32::<5, 6, 7>.slice_axis::<2>(2, 3);
+//! assert_eq!(ShapedArray::new(plane, vec![0; 30]).err(), Some(ShapeError::IncompatibleLayout));
+//! ```
+
+use crate::error::check_len;
+use crate::strided_shape::is_compact;
+use crate::{ConstShape, Shape, ShapeError, ShapeScalar};
+
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+/// An owned buffer of `T` indexed by the `N`-dimensional coordinates of a [`Shape`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShapedArray<T, S> {
+    shape: S,
+    data: Vec<T>,
+}
+
+/// A borrowed buffer of `T` indexed by the `N`-dimensional coordinates of a [`Shape`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ShapedSlice<'a, T, S> {
+    shape: S,
+    data: &'a [T],
+}
+
+/// A mutably borrowed buffer of `T` indexed by the `N`-dimensional coordinates of a [`Shape`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct ShapedSliceMut<'a, T, S> {
+    shape: S,
+    data: &'a mut [T],
+}
+
+/// A stack-allocated array of `T` indexed by the coordinates of a [`ConstShape`].
+///
+/// `LEN` must equal `S::USIZE`, which is checked at compile time when the array is constructed.
+///
+/// ```compile_fail
+/// use ndshape::{ConstShape3u32, ConstShapedArray};
+///
+/// let array = ConstShapedArray::<u8, ConstShape3u32<2, 2, 2>, 7>::new([0; 7]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConstShapedArray<T, S, const LEN: usize> {
+    data: [T; LEN],
+    marker: PhantomData<S>,
+}
+
+impl<T, S> ShapedArray<T, S> {
+    /// Returns [`ShapeError::IncompatibleLayout`] if the linear indices of `shape` are not exactly `0..shape.usize()`, like
+    /// those of a view into a larger shape, or [`ShapeError::LengthMismatch`] if `data.len() != shape.usize()`.
+    pub fn new<const N: usize>(shape: S, data: Vec<T>) -> Result<Self, ShapeError>
+    where
+        S: Shape<N>,
+    {
+        check_layout(&shape, data.len())?;
+        Ok(Self { shape, data })
+    }
+
+    /// Creates an array with every element set to `value`.
+    ///
+    /// Panics if the linear indices of `shape` are not exactly `0..shape.usize()`.
+    pub fn filled<const N: usize>(shape: S, value: T) -> Self
+    where
+        S: Shape<N>,
+        T: Clone,
+    {
+        assert!(
+            is_compact(&shape),
+            "shape does not index a buffer of its own size"
+        );
+        let data = vec![value; shape.usize()];
+        Self { shape, data }
+    }
+
+    pub fn into_parts(self) -> (S, Vec<T>) {
+        (self.shape, self.data)
+    }
+
+    pub fn as_shaped_slice(&self) -> ShapedSlice<'_, T, S>
+    where
+        S: Clone,
+    {
+        ShapedSlice {
+            shape: self.shape.clone(),
+            data: &self.data,
+        }
+    }
+
+    pub fn as_shaped_slice_mut(&mut self) -> ShapedSliceMut<'_, T, S>
+    where
+        S: Clone,
+    {
+        ShapedSliceMut {
+            shape: self.shape.clone(),
+            data: &mut self.data,
+        }
+    }
+}
+
+impl<'a, T, S> ShapedSlice<'a, T, S> {
+    /// Returns [`ShapeError::IncompatibleLayout`] if the linear indices of `shape` are not exactly `0..shape.usize()`, like
+    /// those of a view into a larger shape, or [`ShapeError::LengthMismatch`] if `data.len() != shape.usize()`.
+    pub fn new<const N: usize>(shape: S, data: &'a [T]) -> Result<Self, ShapeError>
+    where
+        S: Shape<N>,
+    {
+        check_layout(&shape, data.len())?;
+        Ok(Self { shape, data })
+    }
+}
+
+impl<'a, T, S> ShapedSliceMut<'a, T, S> {
+    /// Returns [`ShapeError::IncompatibleLayout`] if the linear indices of `shape` are not exactly `0..shape.usize()`, like
+    /// those of a view into a larger shape, or [`ShapeError::LengthMismatch`] if `data.len() != shape.usize()`.
+    pub fn new<const N: usize>(shape: S, data: &'a mut [T]) -> Result<Self, ShapeError>
+    where
+        S: Shape<N>,
+    {
+        check_layout(&shape, data.len())?;
+        Ok(Self { shape, data })
+    }
+}
+
+macro_rules! impl_shaped_read {
+    ([$($lt:lifetime)?] $name:ty) => {
+        impl<$($lt,)? T, S> $name {
+            #[inline]
+            pub fn shape(&self) -> &S {
+                &self.shape
+            }
+
+            #[inline]
+            pub fn as_slice(&self) -> &[T] {
+                &self.data[..]
+            }
+
+            /// The element at `p`, or `None` if any coordinate is outside of the shape.
+            #[inline]
+            pub fn get<const N: usize>(&self, p: [S::Coord; N]) -> Option<&T>
+            where
+                S: Shape<N>,
+            {
+                in_bounds(&self.shape, p).then(|| &self.data[self.shape.linearize(p).to_usize()])
+            }
+
+            /// # Safety
+            ///
+            /// `p` must be inside of the shape.
+            #[inline]
+            pub unsafe fn get_unchecked<const N: usize>(&self, p: [S::Coord; N]) -> &T
+            where
+                S: Shape<N>,
+            {
+                let i = self.shape.linearize(p).to_usize();
+                self.data.get_unchecked(i)
+            }
+
+            /// Iterates over all elements in linear order, along with their coordinates.
+            pub fn indexed_iter<const N: usize>(&self) -> impl Iterator<Item = ([S::Coord; N], &T)>
+            where
+                S: Shape<N>,
+            {
+                self.data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| (self.shape.delinearize(S::Coord::from_usize(i)), value))
+            }
+
+            /// Creates a new array with the same shape by applying `f` to every element.
+            pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> ShapedArray<U, S>
+            where
+                S: Clone,
+            {
+                ShapedArray {
+                    shape: self.shape.clone(),
+                    data: self.data.iter().map(f).collect(),
+                }
+            }
+        }
+
+        impl<$($lt,)? T, S, const N: usize> Index<[S::Coord; N]> for $name
+        where
+            S: Shape<N>,
+        {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, p: [S::Coord; N]) -> &T {
+                self.get(p).expect("coordinate out of bounds")
+            }
+        }
+    };
+}
+
+impl_shaped_read!([] ShapedArray<T, S>);
+impl_shaped_read!(['a] ShapedSlice<'a, T, S>);
+impl_shaped_read!(['a] ShapedSliceMut<'a, T, S>);
+
+macro_rules! impl_shaped_write {
+    ([$($lt:lifetime)?] $name:ty) => {
+        impl<$($lt,)? T, S> $name {
+            #[inline]
+            pub fn as_mut_slice(&mut self) -> &mut [T] {
+                &mut self.data[..]
+            }
+
+            /// The element at `p`, or `None` if any coordinate is outside of the shape.
+            #[inline]
+            pub fn get_mut<const N: usize>(&mut self, p: [S::Coord; N]) -> Option<&mut T>
+            where
+                S: Shape<N>,
+            {
+                if in_bounds(&self.shape, p) {
+                    Some(&mut self.data[self.shape.linearize(p).to_usize()])
+                } else {
+                    None
+                }
+            }
+
+            /// # Safety
+            ///
+            /// `p` must be inside of the shape.
+            #[inline]
+            pub unsafe fn get_unchecked_mut<const N: usize>(&mut self, p: [S::Coord; N]) -> &mut T
+            where
+                S: Shape<N>,
+            {
+                let i = self.shape.linearize(p).to_usize();
+                self.data.get_unchecked_mut(i)
+            }
+
+            /// Sets every element to `value`.
+            pub fn fill(&mut self, value: T)
+            where
+                T: Clone,
+            {
+                self.data.fill(value);
+            }
+
+            /// Iterates mutably over all elements in linear order, along with their coordinates.
+            pub fn indexed_iter_mut<const N: usize>(
+                &mut self,
+            ) -> impl Iterator<Item = ([S::Coord; N], &mut T)>
+            where
+                S: Shape<N>,
+            {
+                let shape = &self.shape;
+                self.data
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(i, value)| (shape.delinearize(S::Coord::from_usize(i)), value))
+            }
+        }
+
+        impl<$($lt,)? T, S, const N: usize> IndexMut<[S::Coord; N]> for $name
+        where
+            S: Shape<N>,
+        {
+            #[inline]
+            fn index_mut(&mut self, p: [S::Coord; N]) -> &mut T {
+                self.get_mut(p).expect("coordinate out of bounds")
+            }
+        }
+    };
+}
+
+impl_shaped_write!([] ShapedArray<T, S>);
+impl_shaped_write!(['a] ShapedSliceMut<'a, T, S>);
+
+impl<T, S, const LEN: usize> ConstShapedArray<T, S, LEN> {
+    /// Fails to compile unless `LEN == S::USIZE`.
+    pub fn new<const N: usize>(data: [T; LEN]) -> Self
+    where
+        S: ConstShape<N>,
+    {
+        const { assert!(LEN == S::USIZE, "LEN must equal the size of the shape") };
+        Self {
+            data,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_inner(self) -> [T; LEN] {
+        self.data
+    }
+
+    /// The element at `p`, or `None` if any coordinate is outside of the shape.
+    #[inline]
+    pub fn get<const N: usize>(&self, p: [S::Coord; N]) -> Option<&T>
+    where
+        S: ConstShape<N>,
+    {
+        const_in_bounds::<S, N>(p).then(|| &self.data[S::linearize(p).to_usize()])
+    }
+
+    /// The element at `p`, or `None` if any coordinate is outside of the shape.
+    #[inline]
+    pub fn get_mut<const N: usize>(&mut self, p: [S::Coord; N]) -> Option<&mut T>
+    where
+        S: ConstShape<N>,
+    {
+        if const_in_bounds::<S, N>(p) {
+            Some(&mut self.data[S::linearize(p).to_usize()])
+        } else {
+            None
+        }
+    }
+
+    /// Sets every element to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.data.fill(value);
+    }
+
+    /// Creates a new array with the same shape by applying `f` to every element.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> ConstShapedArray<U, S, LEN> {
+        ConstShapedArray {
+            data: self.data.map(f),
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates over all elements in linear order, along with their coordinates.
+    pub fn indexed_iter<const N: usize>(&self) -> impl Iterator<Item = ([S::Coord; N], &T)>
+    where
+        S: ConstShape<N>,
+    {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (S::delinearize(S::Coord::from_usize(i)), value))
+    }
+}
+
+impl<T, S, const N: usize, const LEN: usize> Index<[S::Coord; N]> for ConstShapedArray<T, S, LEN>
+where
+    S: ConstShape<N>,
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, p: [S::Coord; N]) -> &T {
+        self.get(p).expect("coordinate out of bounds")
+    }
+}
+
+impl<T, S, const N: usize, const LEN: usize> IndexMut<[S::Coord; N]> for ConstShapedArray<T, S, LEN>
+where
+    S: ConstShape<N>,
+{
+    #[inline]
+    fn index_mut(&mut self, p: [S::Coord; N]) -> &mut T {
+        self.get_mut(p).expect("coordinate out of bounds")
+    }
+}
+
+fn check_layout<S: Shape<N>, const N: usize>(shape: &S, actual: usize) -> Result<(), ShapeError> {
+    if !is_compact(shape) {
+        return Err(ShapeError::IncompatibleLayout);
+    }
+    check_len(shape, actual)
+}
+
+#[inline]
+fn in_bounds<S, const N: usize>(shape: &S, p: [S::Coord; N]) -> bool
+where
+    S: Shape<N>,
+{
+    coords_in_bounds(shape.as_array(), p)
+}
+
+#[inline]
+fn const_in_bounds<S, const N: usize>(p: [S::Coord; N]) -> bool
+where
+    S: ConstShape<N>,
+{
+    coords_in_bounds(S::ARRAY, p)
+}
+
+#[inline]
+fn coords_in_bounds<C: ShapeScalar, const N: usize>(dims: [C; N], p: [C; N]) -> bool {
+    dims.into_iter().zip(p).all(|(d, c)| C::ZERO <= c && c < d)
+}
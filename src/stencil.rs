@@ -0,0 +1,457 @@
+use crate::{BoxIter, ConstShape, Shape, ShapeScalar};
+
+use core::fmt::Debug;
+use core::ops::Range;
+
+/// How a stencil treats neighbors that fall outside of the shape.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BoundaryMode {
+    /// Only visit centers whose whole neighborhood is inside the shape.
+    Skip,
+    /// Replace an outside coordinate with the nearest coordinate on the edge.
+    Clamp,
+    /// Wrap around to the opposite side, as on a torus.
+    Wrap,
+    /// Reflect about the edge without repeating it, so coordinate `-1` reads `1` and `dim` reads `dim - 2`.
+    Mirror,
+}
+
+/// The sliding windows of a kernel over a shape, i.e. the neighborhood of each center point.
+///
+/// The kernel is anchored at its center point, `kernel_dims / 2`. The linear offsets of the kernel points relative to a
+/// center are computed once, so the neighbors of a center whose whole window is inside the shape only cost one addition
+/// each. When the kernel is a [`ConstShape`], [`Shape::iter_const_windows`] keeps the offsets in an array, so the number
+/// of neighbors is a constant and the loop in [`Neighborhood::for_each_index`] can be unrolled.
+///
+/// Returned by [`Shape::iter_windows`]. Iterate over `&Windows` to get a [`Neighborhood`] per center, in row-major order.
+///
+/// ```
+/// use ndshape::{BoundaryMode, ConstShape2u32, ConstShape3u32, Shape};
+///
+/// let shape = ConstShape2u32::<5, 4>;
+/// let data: Vec<u32> = (0..20).collect();
+///
+/// // A 3x3 box blur over the interior.
+/// let windows = shape.iter_windows(ConstShape2u32::<3, 3>, BoundaryMode::Skip);
+/// assert_eq!(windows.offsets()[4], 0);
+/// let mut blurred = Vec::new();
+/// for window in &windows {
+///     let mut sum = 0;
+///     window.for_each_index(|_, i| sum += data[i as usize]);
+///     blurred.push((window.coords(), sum / 9));
+/// }
+/// assert_eq!(blurred.len(), 3 * 2);
+/// assert_eq!(blurred[0], ([1, 1], 6));
+///
+/// // Every center is visited when the boundary is handled by another mode.
+/// let windows = shape.iter_windows(ConstShape2u32::<3, 3>, BoundaryMode::Clamp);
+/// let corner = windows.iter().next().unwrap();
+/// assert!(!corner.is_interior());
+/// let neighbors: Vec<_> = corner.indices().map(|i| shape.delinearize(i)).collect();
+/// assert_eq!(neighbors[0], [0, 0]);
+/// assert_eq!(neighbors[8], [1, 1]);
+/// assert_eq!(windows.iter().count(), 20);
+///
+/// let wrapped = shape.iter_windows(ConstShape2u32::<3, 1>, BoundaryMode::Wrap);
+/// let first: Vec<_> = wrapped.iter().next().unwrap().indices().collect();
+/// assert_eq!(first, [shape.linearize([4, 0]), 0, 1]);
+///
+/// let mirrored = shape.iter_windows(ConstShape2u32::<3, 1>, BoundaryMode::Mirror);
+/// let first: Vec<_> = mirrored.iter().next().unwrap().indices().collect();
+/// assert_eq!(first, [1, 0, 1]);
+///
+/// // A 6-neighborhood is just as easy with a cross-shaped subset of a 3x3x3 kernel.
+/// let volume = ConstShape3u32::<8, 8, 8>;
+/// let windows = volume.iter_windows(ConstShape3u32::<3, 3, 3>, BoundaryMode::Skip);
+/// let faces: Vec<u32> = [4, 10, 12, 14, 16, 22].map(|j| windows.offsets()[j]).to_vec();
+/// assert_eq!(faces, [0u32.wrapping_sub(64), 0u32.wrapping_sub(8), 0u32.wrapping_sub(1), 1, 8, 64]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Windows<'a, S: ?Sized, K, C, const N: usize, O = Vec<C>> {
+    shape: &'a S,
+    kernel: K,
+    mode: BoundaryMode,
+    array: [C; N],
+    anchor: [C; N],
+    points: Vec<[C; N]>,
+    offsets: O,
+}
+
+/// The linear offsets of the kernel points in [`Windows`]: a `Vec` for any kernel, or an array for a [`ConstShape`]
+/// kernel, whose length is a constant.
+pub trait OffsetTable<C>: Clone + Debug {
+    /// A table of `len` zeros.
+    fn zeroed(len: usize) -> Self;
+
+    fn as_slice(&self) -> &[C];
+
+    fn as_mut_slice(&mut self) -> &mut [C];
+
+    /// Calls `f(j, offset)` for each offset in the table.
+    fn for_each(&self, f: impl FnMut(usize, C));
+}
+
+impl<C: ShapeScalar> OffsetTable<C> for Vec<C> {
+    #[inline]
+    fn zeroed(len: usize) -> Self {
+        vec![C::ZERO; len]
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[C] {
+        self
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [C] {
+        self
+    }
+
+    #[inline]
+    fn for_each(&self, mut f: impl FnMut(usize, C)) {
+        for (j, &offset) in self.iter().enumerate() {
+            f(j, offset);
+        }
+    }
+}
+
+impl<C: ShapeScalar, const LEN: usize> OffsetTable<C> for [C; LEN] {
+    #[inline]
+    fn zeroed(len: usize) -> Self {
+        assert_eq!(
+            len, LEN,
+            "the offset table must have one entry per kernel point"
+        );
+        [C::ZERO; LEN]
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[C] {
+        self
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [C] {
+        self
+    }
+
+    #[inline]
+    fn for_each(&self, mut f: impl FnMut(usize, C)) {
+        // The length of the array is a constant, so this loop can be unrolled.
+        for (j, &offset) in self.iter().enumerate() {
+            f(j, offset);
+        }
+    }
+}
+
+impl<'a, S: ?Sized, K, C, const N: usize> Windows<'a, S, K, C, N>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    pub fn new(shape: &'a S, kernel: K, mode: BoundaryMode) -> Self {
+        Self::with_table(shape, kernel, mode)
+    }
+}
+
+impl<'a, S: ?Sized, K, C, const N: usize, const LEN: usize> Windows<'a, S, K, C, N, [C; LEN]>
+where
+    S: Shape<N, Coord = C>,
+    K: ConstShape<N, Coord = C>,
+    C: ShapeScalar,
+{
+    /// Fails to compile unless `LEN == K::USIZE`.
+    pub fn new_const(shape: &'a S, kernel: K, mode: BoundaryMode) -> Self {
+        const { assert!(LEN == K::USIZE, "LEN must equal the size of the kernel") };
+        Self::with_table(shape, kernel, mode)
+    }
+}
+
+impl<'a, S: ?Sized, K, C, const N: usize, O> Windows<'a, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+    fn with_table(shape: &'a S, kernel: K, mode: BoundaryMode) -> Self {
+        let two = C::ONE + C::ONE;
+        let anchor = kernel.as_array().map(|dim| dim / two);
+        let origin = shape.linearize(anchor);
+        let mut points = vec![[C::ZERO; N]; kernel.usize()];
+        let mut offsets = O::zeroed(kernel.usize());
+        for (j, k) in BoxIter::new(&kernel, [C::ZERO; N], kernel.as_array()) {
+            points[j.to_usize()] = k;
+            offsets.as_mut_slice()[j.to_usize()] = shape.linearize(k).wrapping_sub(origin);
+        }
+        Self {
+            shape,
+            kernel,
+            mode,
+            array: shape.as_array(),
+            anchor,
+            points,
+            offsets,
+        }
+    }
+
+    #[inline]
+    pub fn shape(&self) -> &'a S {
+        self.shape
+    }
+
+    #[inline]
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+
+    #[inline]
+    pub fn mode(&self) -> BoundaryMode {
+        self.mode
+    }
+
+    /// The linear offset of each kernel point from the center, indexed by the kernel's linear index. Adding these to
+    /// the index of an interior center, with wrapping, gives the indices of its neighbors.
+    #[inline]
+    pub fn offsets(&self) -> &[C] {
+        self.offsets.as_slice()
+    }
+
+    /// Whether the whole window centered on `p` is inside the shape.
+    #[inline]
+    pub fn is_interior(&self, p: [C; N]) -> bool {
+        let kernel = self.kernel.as_array();
+        (0..N).all(|k| {
+            let after = kernel[k] - C::ONE - self.anchor[k];
+            p[k] >= self.anchor[k] && p[k] + after < self.array[k]
+        })
+    }
+
+    pub fn iter(&self) -> WindowIter<'_, S, K, C, N, O> {
+        let (min, centers) = if self.mode == BoundaryMode::Skip {
+            let kernel = self.kernel.as_array();
+            let mut centers = [C::ZERO; N];
+            for k in 0..N {
+                if kernel[k] <= self.array[k] {
+                    centers[k] = self.array[k] - kernel[k] + C::ONE;
+                }
+            }
+            (self.anchor, centers)
+        } else {
+            ([C::ZERO; N], self.array)
+        };
+        WindowIter {
+            windows: self,
+            centers: BoxIter::new(self.shape, min, centers),
+        }
+    }
+
+    /// Maps the coordinate `c + k - anchor` along `axis` back into the shape according to the boundary mode.
+    #[inline]
+    fn resolve(&self, axis: usize, c: C, k: C) -> C {
+        let (dim, anchor) = (self.array[axis], self.anchor[axis]);
+        let reached = c + k;
+        if reached >= anchor {
+            let q = reached - anchor;
+            if q < dim {
+                return q;
+            }
+            match self.mode {
+                BoundaryMode::Clamp => dim - C::ONE,
+                BoundaryMode::Wrap => q % dim,
+                BoundaryMode::Mirror => mirror(q, dim),
+                BoundaryMode::Skip => unreachable!("skipped windows are inside the shape"),
+            }
+        } else {
+            let before = anchor - reached;
+            match self.mode {
+                BoundaryMode::Clamp => C::ZERO,
+                BoundaryMode::Wrap => (dim - before % dim) % dim,
+                BoundaryMode::Mirror => mirror(before, dim),
+                BoundaryMode::Skip => unreachable!("skipped windows are inside the shape"),
+            }
+        }
+    }
+}
+
+/// Reflects the distance `x` from coordinate 0 (in either direction) into `0..dim` without repeating the edges.
+#[inline]
+fn mirror<C: ShapeScalar>(x: C, dim: C) -> C {
+    if dim == C::ONE {
+        return C::ZERO;
+    }
+    let period = (dim - C::ONE) + (dim - C::ONE);
+    let x = x % period;
+    if x < dim {
+        x
+    } else {
+        period - x
+    }
+}
+
+impl<'a, S: ?Sized, K, C, const N: usize, O> IntoIterator for &'a Windows<'_, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+    type Item = Neighborhood<'a, S, K, C, N, O>;
+    type IntoIter = WindowIter<'a, S, K, C, N, O>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the centers of [`Windows`] in row-major order.
+#[derive(Clone, Debug)]
+pub struct WindowIter<'a, S: ?Sized, K, C, const N: usize, O = Vec<C>> {
+    windows: &'a Windows<'a, S, K, C, N, O>,
+    centers: BoxIter<'a, S, C, N>,
+}
+
+impl<'a, S: ?Sized, K, C, const N: usize, O> Iterator for WindowIter<'a, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+    type Item = Neighborhood<'a, S, K, C, N, O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (center, coords) = self.centers.next()?;
+        Some(Neighborhood {
+            windows: self.windows,
+            center,
+            coords,
+            is_interior: self.windows.mode == BoundaryMode::Skip
+                || self.windows.is_interior(coords),
+        })
+    }
+}
+
+/// The window centered on one point of a shape.
+#[derive(Debug)]
+pub struct Neighborhood<'a, S: ?Sized, K, C, const N: usize, O = Vec<C>> {
+    windows: &'a Windows<'a, S, K, C, N, O>,
+    center: C,
+    coords: [C; N],
+    is_interior: bool,
+}
+
+impl<S: ?Sized, K, C: Copy, const N: usize, O> Clone for Neighborhood<'_, S, K, C, N, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ?Sized, K, C: Copy, const N: usize, O> Copy for Neighborhood<'_, S, K, C, N, O> {}
+
+impl<'a, S: ?Sized, K, C, const N: usize, O> Neighborhood<'a, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+    /// The linear index of the center.
+    #[inline]
+    pub fn center(&self) -> C {
+        self.center
+    }
+
+    #[inline]
+    pub fn coords(&self) -> [C; N] {
+        self.coords
+    }
+
+    /// Whether the whole window is inside the shape, so the neighbors are found with the precomputed offsets.
+    #[inline]
+    pub fn is_interior(&self) -> bool {
+        self.is_interior
+    }
+
+    /// The linear index in the shape of the neighbor at kernel index `j`.
+    #[inline]
+    pub fn index(&self, j: usize) -> C {
+        if self.is_interior {
+            return self.center.wrapping_add(self.windows.offsets.as_slice()[j]);
+        }
+        let k = self.windows.points[j];
+        let mut p = self.coords;
+        for (axis, c) in p.iter_mut().enumerate() {
+            *c = self.windows.resolve(axis, *c, k[axis]);
+        }
+        self.windows.shape.linearize(p)
+    }
+
+    /// Calls `f(j, i)` for each kernel index `j` with the linear index `i` of the corresponding neighbor.
+    #[inline]
+    pub fn for_each_index(&self, mut f: impl FnMut(usize, C)) {
+        if self.is_interior {
+            self.windows
+                .offsets
+                .for_each(|j, offset| f(j, self.center.wrapping_add(offset)));
+        } else {
+            self.windows.offsets.for_each(|j, _| f(j, self.index(j)));
+        }
+    }
+
+    /// The linear indices of the neighbors, in the kernel's linear order.
+    #[inline]
+    pub fn indices(&self) -> NeighborIndices<'a, S, K, C, N, O> {
+        NeighborIndices {
+            neighborhood: *self,
+            range: 0..self.windows.offsets.as_slice().len(),
+        }
+    }
+}
+
+/// An iterator over the linear indices of the neighbors in a [`Neighborhood`].
+#[derive(Debug)]
+pub struct NeighborIndices<'a, S: ?Sized, K, C, const N: usize, O = Vec<C>> {
+    neighborhood: Neighborhood<'a, S, K, C, N, O>,
+    range: Range<usize>,
+}
+
+impl<S: ?Sized, K, C: Copy, const N: usize, O> Clone for NeighborIndices<'_, S, K, C, N, O> {
+    fn clone(&self) -> Self {
+        Self {
+            neighborhood: self.neighborhood,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<S: ?Sized, K, C, const N: usize, O> Iterator for NeighborIndices<'_, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+    type Item = C;
+
+    #[inline]
+    fn next(&mut self) -> Option<C> {
+        self.range.next().map(|j| self.neighborhood.index(j))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<S: ?Sized, K, C, const N: usize, O> ExactSizeIterator for NeighborIndices<'_, S, K, C, N, O>
+where
+    S: Shape<N, Coord = C>,
+    K: Shape<N, Coord = C>,
+    C: ShapeScalar,
+    O: OffsetTable<C>,
+{
+}
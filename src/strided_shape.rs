@@ -0,0 +1,240 @@
+use crate::scalar::{is_negative, magnitude};
+use crate::{decompose, Shape, ShapeError, ShapeScalar};
+
+use core::ops::Range;
+
+/// A shape with explicit strides and a base offset, which is the general form of a view into another shape's buffer.
+///
+/// `linearize(p) = offset + strides[0] * p[0] + strides[1] * p[1] + ...`
+///
+/// Strides may be negative, e.g. for the axes of a [`Flipped`](crate::Flipped) shape, including as wrapped values with
+/// unsigned coordinates. [`Shape::delinearize`] is only the inverse of `linearize` when no two elements share a linear
+/// index, like any view produced by this crate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StridedShape<C, const N: usize> {
+    array: [C; N],
+    strides: [C; N],
+    offset: C,
+    size: C,
+    /// Axes sorted by decreasing absolute stride, for delinearization.
+    order: [usize; N],
+}
+
+impl<C: ShapeScalar, const N: usize> StridedShape<C, N> {
+    pub fn new(array: [C; N], strides: [C; N], offset: C) -> Self {
+        let mut order = [0; N];
+        for (i, axis) in order.iter_mut().enumerate() {
+            *axis = i;
+        }
+        order.sort_by_key(|&axis| core::cmp::Reverse(magnitude(strides[axis])));
+        let size = array.iter().fold(C::ONE, |size, &dim| size * dim);
+        Self {
+            array,
+            strides,
+            offset,
+            size,
+            order,
+        }
+    }
+
+    /// The linear index of the origin.
+    #[inline]
+    pub fn offset(&self) -> C {
+        self.offset
+    }
+}
+
+impl<C: ShapeScalar, const N: usize> Shape<N> for StridedShape<C, N> {
+    type Coord = C;
+
+    #[inline]
+    fn size(&self) -> C {
+        self.size
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.size.to_usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [C; N] {
+        self.array
+    }
+
+    #[inline]
+    fn strides(&self) -> [C; N] {
+        self.strides
+    }
+
+    #[inline]
+    fn linearize(&self, p: [C; N]) -> C {
+        self.strides
+            .iter()
+            .zip(p)
+            .fold(self.offset, |i, (&stride, c)| {
+                i.wrapping_add(stride.wrapping_mul(c))
+            })
+    }
+
+    #[inline]
+    fn delinearize(&self, i: C) -> [C; N] {
+        // Reflect axes with negative strides, since `-s * c = s * (dim - 1 - c) - s * (dim - 1)`, so that every stride
+        // is positive.
+        let mut i = i.wrapping_sub(self.offset);
+        for (&stride, &dim) in self.strides.iter().zip(self.array.iter()) {
+            if is_negative(stride) && dim > C::ZERO {
+                i = i.wrapping_add(magnitude(stride).wrapping_mul(dim - C::ONE));
+            }
+        }
+        let mut p = [C::ZERO; N];
+        for &axis in self.order.iter() {
+            let stride = magnitude(self.strides[axis]);
+            if stride != C::ZERO {
+                p[axis] = i / stride;
+                i = i - p[axis] * stride;
+            }
+        }
+        for (axis, c) in p.iter_mut().enumerate() {
+            if is_negative(self.strides[axis]) && self.array[axis] > C::ZERO {
+                *c = self.array[axis] - C::ONE - *c;
+            }
+        }
+        p
+    }
+
+    /// [`Shape::delinearize`], or `None` if no point of the view has linear index `i`.
+    ///
+    /// ```
+    /// use ndshape::{ConstShape3u32, Shape};
+    ///
+    /// let shape = ConstShape3u32::<5, 6, 7>;
+    /// let plane = shape.slice_axis::<2>(2, 3);
+    /// assert_eq!(plane.try_delinearize(shape.linearize([1, 2, 3])), Some([1, 2]));
+    /// assert_eq!(plane.try_delinearize(shape.linearize([1, 2, 4])), None);
+    ///
+    /// let half = ConstShape3u32::<8, 8, 8>.step_view([0, 0, 0], [2, 2, 2], [4, 4, 4]);
+    /// assert_eq!(half.try_delinearize(418), Some([1, 2, 3]));
+    /// assert_eq!(half.try_delinearize(419), None);
+    /// ```
+    fn try_delinearize(&self, i: C) -> Option<[C; N]> {
+        let p = self.delinearize(i);
+        let in_bounds = p
+            .iter()
+            .zip(self.array)
+            .all(|(&c, dim)| C::ZERO <= c && c < dim);
+        (in_bounds && self.linearize(p) == i).then_some(p)
+    }
+
+    #[inline]
+    fn slice_axis<const M: usize>(&self, axis: usize, index: C) -> StridedShape<C, M> {
+        slice_axis(self.array, self.strides, self.offset, axis, index)
+    }
+
+    fn linear_range_to_boxes(&self, range: Range<C>) -> Result<Vec<([C; N], [C; N])>, ShapeError> {
+        if self.offset != C::ZERO || self.strides != row_major_strides(self.array) {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        Ok(decompose::linear_range_to_boxes(self.array, range))
+    }
+
+    fn box_to_runs(&self, min: [C; N], box_shape: [C; N]) -> Vec<(C, C)> {
+        decompose::box_to_runs(self, self.strides, min, box_shape)
+    }
+
+    #[inline]
+    fn broadcast_to<const M: usize>(
+        &self,
+        target: [C; M],
+    ) -> Result<StridedShape<C, M>, ShapeError> {
+        broadcast(self.array, self.strides, self.offset, target)
+    }
+}
+
+/// Removes `axis` from a strided layout by fixing its coordinate to `index`.
+pub(crate) fn slice_axis<C: ShapeScalar, const N: usize, const M: usize>(
+    array: [C; N],
+    strides: [C; N],
+    offset: C,
+    axis: usize,
+    index: C,
+) -> StridedShape<C, M> {
+    const { assert!(M + 1 == N, "slicing must remove exactly one axis") };
+    assert!(axis < N, "axis {axis} is out of bounds for {N} dimensions");
+    assert!(
+        C::ZERO <= index && index < array[axis],
+        "index {index:?} is out of bounds for axis {axis} of length {:?}",
+        array[axis]
+    );
+    let mut new_array = [C::ZERO; M];
+    let mut new_strides = [C::ZERO; M];
+    for i in 0..M {
+        let j = if i < axis { i } else { i + 1 };
+        new_array[i] = array[j];
+        new_strides[i] = strides[j];
+    }
+    let offset = offset.wrapping_add(strides[axis].wrapping_mul(index));
+    StridedShape::new(new_array, new_strides, offset)
+}
+
+/// Broadcasts a strided layout to `target` dimensions by giving a zero stride to every axis that is stretched from 1 or
+/// missing.
+pub(crate) fn broadcast<C: ShapeScalar, const N: usize, const M: usize>(
+    array: [C; N],
+    strides: [C; N],
+    offset: C,
+    target: [C; M],
+) -> Result<StridedShape<C, M>, ShapeError> {
+    const { assert!(N <= M, "cannot broadcast to fewer dimensions") };
+    let mut new_strides = [C::ZERO; M];
+    for i in 0..N {
+        if array[i] == target[i] {
+            new_strides[i] = strides[i];
+        } else if array[i] != C::ONE {
+            return Err(ShapeError::NotBroadcastable);
+        }
+    }
+    Ok(StridedShape::new(target, new_strides, offset))
+}
+
+/// The strides of a row-major layout with dimensions `array`.
+pub(crate) fn row_major_strides<C: ShapeScalar, const N: usize>(array: [C; N]) -> [C; N] {
+    let mut strides = [C::ONE; N];
+    for i in 1..N {
+        strides[i] = strides[i - 1] * array[i - 1];
+    }
+    strides
+}
+
+/// Whether the linear indices of `shape` are exactly `0..shape.usize()`, for any order and direction of the axes.
+pub(crate) fn is_compact<S: Shape<N>, const N: usize>(shape: &S) -> bool {
+    if shape.is_empty() {
+        return true;
+    }
+    let array = shape.as_array();
+    let strides = shape.strides();
+    let mut first = shape.linearize([S::Coord::ZERO; N]);
+    for (&stride, &dim) in strides.iter().zip(array.iter()) {
+        if is_negative(stride) && dim > S::Coord::ONE {
+            first = first.wrapping_add(stride.wrapping_mul(dim - S::Coord::ONE));
+        }
+    }
+    if first != S::Coord::ZERO {
+        return false;
+    }
+    let mut order = [0; N];
+    for (i, axis) in order.iter_mut().enumerate() {
+        *axis = i;
+    }
+    order.sort_by_key(|&axis| magnitude(strides[axis]));
+    let mut expected = S::Coord::ONE;
+    for axis in order {
+        if array[axis] > S::Coord::ONE {
+            if magnitude(strides[axis]) != expected {
+                return false;
+            }
+            expected = expected * array[axis];
+        }
+    }
+    true
+}
@@ -0,0 +1,289 @@
+//! Shapes that read another shape's buffer through a mirrored or rotated coordinate mapping.
+//!
+//! ```
+//! use ndshape::{gather_into, ConstShape2u32, Flipped, Rotated, Shape};
+//!
+//! // A 3 x 2 prefab, stored row-major.
+//! let prefab = ConstShape2u32::<3, 2>;
+//! let data = ['a', 'b', 'c', 'd', 'e', 'f'];
+//!
+//! let rotated = Rotated::new(prefab, [0, 1], 1);
+//! assert_eq!(rotated.as_array(), [2, 3]);
+//! let mut out = ['.'; 6];
+//! gather_into(&rotated, &data, &mut out).unwrap();
+//! assert_eq!(out, ['d', 'a', 'e', 'b', 'f', 'c']);
+//!
+//! let mirrored = Flipped::new(prefab, [true, false]);
+//! gather_into(&mirrored, &data, &mut out).unwrap();
+//! assert_eq!(out, ['c', 'b', 'a', 'f', 'e', 'd']);
+//! ```
+
+use crate::error::check_len;
+use crate::{BoxIter, Shape, ShapeError, ShapeScalar};
+
+use core::ops::Range;
+
+/// A view of `S` with some axes mirrored, so coordinate `c` along a flipped axis reads the source at `dim - 1 - c`.
+///
+/// Linearizing gives indices into the buffer of the source shape.
+///
+/// ```
+/// use ndshape::{ConstShape3u32, Flipped, Shape};
+///
+/// let shape = ConstShape3u32::<5, 6, 7>;
+/// let flipped = Flipped::new(shape, [true, false, true]);
+/// assert_eq!(flipped.as_array(), [5, 6, 7]);
+/// assert_eq!(flipped.linearize([0, 2, 0]), shape.linearize([4, 2, 6]));
+/// assert_eq!(flipped.delinearize(shape.linearize([4, 2, 6])), [0, 2, 0]);
+///
+/// // Views of a flipped shape keep its negative strides.
+/// let plane = flipped.slice_axis::<2>(1, 3);
+/// let index = plane.linearize([1, 2]);
+/// assert_eq!(index, shape.linearize([3, 3, 4]));
+/// assert_eq!(plane.delinearize(index), [1, 2]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Flipped<S> {
+    shape: S,
+    /// Bit `k` is set if axis `k` is flipped.
+    mask: u64,
+}
+
+impl<S> Flipped<S> {
+    pub fn new<const N: usize>(shape: S, axes: [bool; N]) -> Self
+    where
+        S: Shape<N>,
+    {
+        let mask = axes
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (k, &flip)| mask | (u64::from(flip) << k));
+        Self { shape, mask }
+    }
+
+    /// The source shape.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.shape
+    }
+
+    #[inline]
+    pub fn is_flipped(&self, axis: usize) -> bool {
+        self.mask & (1 << axis) != 0
+    }
+
+    #[inline]
+    fn flip<C: ShapeScalar, const N: usize>(&self, p: [C; N], array: [C; N]) -> [C; N] {
+        let mut q = p;
+        for k in 0..N {
+            if self.is_flipped(k) {
+                q[k] = (array[k] - C::ONE).wrapping_sub(p[k]);
+            }
+        }
+        q
+    }
+}
+
+impl<S: Shape<N>, const N: usize> Shape<N> for Flipped<S> {
+    type Coord = S::Coord;
+
+    #[inline]
+    fn size(&self) -> S::Coord {
+        self.shape.size()
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.shape.usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [S::Coord; N] {
+        self.shape.as_array()
+    }
+
+    #[inline]
+    fn strides(&self) -> [S::Coord; N] {
+        let mut strides = self.shape.strides();
+        for (k, stride) in strides.iter_mut().enumerate() {
+            if self.is_flipped(k) {
+                *stride = S::Coord::ZERO.wrapping_sub(*stride);
+            }
+        }
+        strides
+    }
+
+    #[inline]
+    fn linearize(&self, p: [S::Coord; N]) -> S::Coord {
+        self.shape.linearize(self.flip(p, self.shape.as_array()))
+    }
+
+    #[inline]
+    fn delinearize(&self, i: S::Coord) -> [S::Coord; N] {
+        self.flip(self.shape.delinearize(i), self.shape.as_array())
+    }
+
+    fn linear_range_to_boxes(
+        &self,
+        range: Range<S::Coord>,
+    ) -> Result<Vec<([S::Coord; N], [S::Coord; N])>, ShapeError> {
+        if self.mask != 0 {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        self.shape.linear_range_to_boxes(range)
+    }
+}
+
+/// A view of `S` rotated by quarter turns in the plane of two axes, where one turn rotates the first axis of the plane
+/// onto the second.
+///
+/// The dimensions of the two axes are swapped after an odd number of turns. Linearizing gives indices into the buffer of
+/// the source shape.
+///
+/// ```
+/// use ndshape::{ConstShape3u32, Rotated, Shape};
+///
+/// let shape = ConstShape3u32::<5, 6, 7>;
+/// let rotated = Rotated::new(shape, [0, 2], 1);
+/// assert_eq!(rotated.as_array(), [7, 6, 5]);
+/// assert_eq!(rotated.linearize([0, 1, 2]), shape.linearize([2, 1, 6]));
+/// assert_eq!(rotated.delinearize(shape.linearize([2, 1, 6])), [0, 1, 2]);
+///
+/// let p = [3, 4, 2];
+/// assert_eq!(Rotated::new(shape, [0, 2], 4).linearize(p), shape.linearize(p));
+/// assert_eq!(
+///     Rotated::new(shape, [0, 2], -1).linearize(p),
+///     Rotated::new(shape, [0, 2], 3).linearize(p)
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rotated<S> {
+    shape: S,
+    plane: [usize; 2],
+    turns: u8,
+}
+
+impl<S> Rotated<S> {
+    /// Rotates by `quarter_turns` in the plane of the two axes in `plane`. Negative turns rotate the other way.
+    pub fn new<const N: usize>(shape: S, plane: [usize; 2], quarter_turns: i32) -> Self
+    where
+        S: Shape<N>,
+    {
+        let [a, b] = plane;
+        assert!(
+            a < N && b < N && a != b,
+            "plane {plane:?} must be two different axes of {N} dimensions"
+        );
+        Self {
+            shape,
+            plane,
+            turns: quarter_turns.rem_euclid(4) as u8,
+        }
+    }
+
+    /// The source shape.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.shape
+    }
+}
+
+impl<S: Shape<N>, const N: usize> Shape<N> for Rotated<S> {
+    type Coord = S::Coord;
+
+    #[inline]
+    fn size(&self) -> S::Coord {
+        self.shape.size()
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.shape.usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [S::Coord; N] {
+        let mut array = self.shape.as_array();
+        if self.turns % 2 == 1 {
+            array.swap(self.plane[0], self.plane[1]);
+        }
+        array
+    }
+
+    #[inline]
+    fn strides(&self) -> [S::Coord; N] {
+        let [a, b] = self.plane;
+        let mut strides = self.shape.strides();
+        let (sa, sb) = (strides[a], strides[b]);
+        let neg = |s: S::Coord| S::Coord::ZERO.wrapping_sub(s);
+        match self.turns {
+            1 => (strides[a], strides[b]) = (neg(sb), sa),
+            2 => (strides[a], strides[b]) = (neg(sa), neg(sb)),
+            3 => (strides[a], strides[b]) = (sb, neg(sa)),
+            _ => {}
+        }
+        strides
+    }
+
+    #[inline]
+    fn linearize(&self, q: [S::Coord; N]) -> S::Coord {
+        let [a, b] = self.plane;
+        let src = self.shape.as_array();
+        let last = |k: usize| src[k] - S::Coord::ONE;
+        let mut p = q;
+        match self.turns {
+            1 => (p[a], p[b]) = (q[b], last(b).wrapping_sub(q[a])),
+            2 => (p[a], p[b]) = (last(a).wrapping_sub(q[a]), last(b).wrapping_sub(q[b])),
+            3 => (p[a], p[b]) = (last(a).wrapping_sub(q[b]), q[a]),
+            _ => {}
+        }
+        self.shape.linearize(p)
+    }
+
+    #[inline]
+    fn delinearize(&self, i: S::Coord) -> [S::Coord; N] {
+        let [a, b] = self.plane;
+        let src = self.shape.as_array();
+        let last = |k: usize| src[k] - S::Coord::ONE;
+        let p = self.shape.delinearize(i);
+        let mut q = p;
+        match self.turns {
+            1 => (q[a], q[b]) = (last(b).wrapping_sub(p[b]), p[a]),
+            2 => (q[a], q[b]) = (last(a).wrapping_sub(p[a]), last(b).wrapping_sub(p[b])),
+            3 => (q[a], q[b]) = (p[b], last(a).wrapping_sub(p[a])),
+            _ => {}
+        }
+        q
+    }
+
+    fn linear_range_to_boxes(
+        &self,
+        range: Range<S::Coord>,
+    ) -> Result<Vec<([S::Coord; N], [S::Coord; N])>, ShapeError> {
+        if self.turns != 0 {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        self.shape.linear_range_to_boxes(range)
+    }
+}
+
+/// Copies every element of `src` as seen through `view` into `dst`, in row-major order of the view's coordinates.
+/// Afterwards `dst` is a compact array with dimensions `view.as_array()`, e.g. a rotated copy of `src`.
+///
+/// Returns [`ShapeError::LengthMismatch`] if `dst.len() != view.usize()`. Panics if `view` reads outside of `src`.
+pub fn gather_into<S, T, const N: usize>(
+    view: &S,
+    src: &[T],
+    dst: &mut [T],
+) -> Result<(), ShapeError>
+where
+    S: Shape<N>,
+    T: Clone,
+{
+    check_len(view, dst.len())?;
+    let points = BoxIter::new(view, [S::Coord::ZERO; N], view.as_array());
+    for (out, (i, _)) in dst.iter_mut().zip(points) {
+        *out = src[i.to_usize()].clone();
+    }
+    Ok(())
+}
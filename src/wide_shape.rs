@@ -0,0 +1,224 @@
+use crate::{ConstShape, RuntimeShape, Shape, ShapeScalar};
+
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+/// A shape whose coordinates can be stored in a narrower type than its linear indices.
+///
+/// The shape itself is a [`Shape`] over the wide index type, so every algorithm in this crate works with it on widened
+/// coordinates. This trait adds the compact coordinate type, and translates compact coordinates directly by widening each
+/// one before it is multiplied by its stride.
+pub trait IndexShape<const N: usize>: Shape<N> {
+    /// The type of compact coordinates, which widens to [`Shape::Coord`] without loss.
+    type Compact: ShapeScalar + Into<Self::Coord> + TryFrom<Self::Coord>;
+
+    /// The dimensions of the shape as compact coordinates.
+    #[inline]
+    fn compact_array(&self) -> [Self::Compact; N] {
+        self.as_array().map(narrow)
+    }
+
+    /// Translates compact coordinates into a wide linear index.
+    #[inline]
+    fn linearize_wide(&self, p: [Self::Compact; N]) -> Self::Coord {
+        self.linearize(p.map(Into::into))
+    }
+
+    /// The inverse of `linearize_wide`. Panics if a coordinate does not fit in the compact type, which only happens if
+    /// `i` is not in `0..self.size()`.
+    #[inline]
+    fn delinearize_wide(&self, i: Self::Coord) -> [Self::Compact; N] {
+        self.delinearize(i).map(narrow)
+    }
+}
+
+#[inline]
+fn narrow<C: TryFrom<I>, I: Copy + Debug>(c: I) -> C {
+    match C::try_from(c) {
+        Ok(c) => c,
+        Err(_) => panic!("coordinate {c:?} does not fit in the compact type"),
+    }
+}
+
+/// The row-major shape of an `N`-dimensional array with compact coordinates of type `C` and linear indices of type `I`.
+///
+/// Compact coordinates are widened to `I` before they are multiplied by their strides, so e.g. `u8` coordinates can
+/// address arrays with more than 256 elements. As a [`Shape`], coordinates have type `I`.
+///
+/// ```
+/// use ndshape::{BoxIter, ConstShape3u8, IndexShape, Shape, WideShape};
+///
+/// let shape = WideShape::<u8, u32, 3>::new([200, 200, 200]);
+/// assert_eq!(shape.size(), 8_000_000);
+/// assert_eq!(shape.compact_array(), [200, 200, 200]);
+///
+/// let index = shape.linearize_wide([199, 198, 197]);
+/// assert_eq!(index, 199 + 200 * 198 + 40_000 * 197);
+/// assert_eq!(shape.delinearize_wide(index), [199, 198, 197]);
+///
+/// // Generic algorithms run on the widened coordinates.
+/// assert_eq!(shape.linearize([199, 198, 197]), index);
+/// let last = BoxIter::new(&shape, [199, 199, 199], [1, 1, 1]).next();
+/// assert_eq!(last, Some((7_999_999, [199, 199, 199])));
+///
+/// // Signed coordinates are sign-extended.
+/// let shape = WideShape::<i64, i128, 2>::new([10, 10]);
+/// assert_eq!(shape.linearize_wide([0, -1]), -10);
+///
+/// let shape = WideShape::<u8, usize, 3>::from_shape(&ConstShape3u8::<16, 16, 16>);
+/// assert_eq!(shape.linearize_wide([15, 15, 15]), 4095);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WideShape<C, I, const N: usize> {
+    shape: RuntimeShape<I, N>,
+    compact: PhantomData<C>,
+}
+
+impl<C, I, const N: usize> WideShape<C, I, N>
+where
+    C: ShapeScalar + Into<I>,
+    I: ShapeScalar,
+{
+    pub fn new(array: [C; N]) -> Self {
+        Self {
+            shape: RuntimeShape::new(array.map(Into::into)),
+            compact: PhantomData,
+        }
+    }
+
+    /// A shape with the same dimensions as `shape` but indices of type `I`.
+    pub fn from_shape<S: Shape<N, Coord = C>>(shape: &S) -> Self {
+        Self::new(shape.as_array())
+    }
+}
+
+impl<C: ShapeScalar, I: ShapeScalar, const N: usize> Shape<N> for WideShape<C, I, N> {
+    type Coord = I;
+
+    #[inline]
+    fn size(&self) -> I {
+        self.shape.size()
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.shape.usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [I; N] {
+        self.shape.as_array()
+    }
+
+    #[inline]
+    fn strides(&self) -> [I; N] {
+        self.shape.strides()
+    }
+
+    #[inline]
+    fn linearize(&self, p: [I; N]) -> I {
+        self.shape.linearize(p)
+    }
+
+    #[inline]
+    fn delinearize(&self, i: I) -> [I; N] {
+        self.shape.delinearize(i)
+    }
+}
+
+impl<C, I, const N: usize> IndexShape<N> for WideShape<C, I, N>
+where
+    C: ShapeScalar + Into<I> + TryFrom<I>,
+    I: ShapeScalar,
+{
+    type Compact = C;
+}
+
+/// The row-major shape of a [`ConstShape`] `S` with linear indices of type `I`, for compact coordinates whose index would
+/// overflow the coordinate type.
+///
+/// Only the dimensions of `S` are used, so e.g. `ConstShape3u8<200, 200, 200>` works although its own size does not fit
+/// in a `u8`. As a [`Shape`], coordinates have type `I`.
+///
+/// ```
+/// use ndshape::{ConstShape3u8, ConstWideShape, IndexShape, Shape};
+///
+/// let shape = ConstWideShape::<ConstShape3u8<200, 200, 200>, u32>::new();
+/// assert_eq!(shape.size(), 8_000_000);
+///
+/// let index = shape.linearize_wide([199, 198, 197]);
+/// assert_eq!(index, 199 + 200 * 198 + 40_000 * 197);
+/// assert_eq!(shape.delinearize_wide(index), [199, 198, 197]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ConstWideShape<S, I> {
+    marker: PhantomData<(S, I)>,
+}
+
+impl<S, I> ConstWideShape<S, I> {
+    pub const fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The layout of a [`ConstWideShape`] with the dimensions known only at runtime. The dimensions are constants, so this
+/// folds away.
+#[inline]
+fn runtime<S, I, const N: usize>() -> RuntimeShape<I, N>
+where
+    S: ConstShape<N>,
+    S::Coord: Into<I>,
+    I: ShapeScalar,
+{
+    RuntimeShape::new(S::ARRAY.map(Into::into))
+}
+
+impl<S, I, const N: usize> Shape<N> for ConstWideShape<S, I>
+where
+    S: ConstShape<N>,
+    S::Coord: Into<I>,
+    I: ShapeScalar,
+{
+    type Coord = I;
+
+    #[inline]
+    fn size(&self) -> I {
+        runtime::<S, I, N>().size()
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        runtime::<S, I, N>().usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [I; N] {
+        S::ARRAY.map(Into::into)
+    }
+
+    #[inline]
+    fn strides(&self) -> [I; N] {
+        runtime::<S, I, N>().strides()
+    }
+
+    #[inline]
+    fn linearize(&self, p: [I; N]) -> I {
+        runtime::<S, I, N>().linearize(p)
+    }
+
+    #[inline]
+    fn delinearize(&self, i: I) -> [I; N] {
+        runtime::<S, I, N>().delinearize(i)
+    }
+}
+
+impl<S, I, const N: usize> IndexShape<N> for ConstWideShape<S, I>
+where
+    S: ConstShape<N>,
+    S::Coord: Into<I> + TryFrom<I>,
+    I: ShapeScalar,
+{
+    type Compact = S::Coord;
+}
@@ -0,0 +1,133 @@
+use crate::{Shape, ShapeError, ShapeScalar};
+
+use core::ops::Range;
+
+/// A box inside of a parent shape that acts as a shape of its own, e.g. the interior of a padded chunk.
+///
+/// Linearizing local coordinates gives the linear index of the same point in the parent, so the window indexes the
+/// parent's buffer directly, and delinearizing a parent index gives local coordinates.
+///
+/// ```
+/// use ndshape::{ConstShape3u32, Shape, Window};
+///
+/// // A 16^3 chunk padded by one voxel on each side.
+/// let padded = ConstShape3u32::<18, 18, 18>;
+/// let interior = Window::new(padded, [1, 1, 1], [16, 16, 16]);
+/// assert_eq!(interior.as_array(), [16, 16, 16]);
+///
+/// let index = interior.linearize([0, 2, 15]);
+/// assert_eq!(index, padded.linearize([1, 3, 16]));
+/// assert_eq!(interior.delinearize(index), [0, 2, 15]);
+///
+/// // Generic algorithms run on the window unchanged.
+/// let faces: Vec<_> = interior.iter_boundary().map(|(i, _)| i).collect();
+/// assert_eq!(faces.len(), 16 * 16 * 16 - 14 * 14 * 14);
+/// assert!(faces.iter().all(|&i| {
+///     let p = padded.delinearize(i);
+///     p.iter().any(|&c| c == 1 || c == 16)
+/// }));
+///
+/// assert_eq!(interior.try_delinearize(padded.linearize([0, 5, 5])), None);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Window<S: Shape<N>, const N: usize> {
+    parent: S,
+    min: [S::Coord; N],
+    array: [S::Coord; N],
+    size: S::Coord,
+}
+
+impl<S: Shape<N>, const N: usize> Window<S, N> {
+    /// The box of `parent` starting at `min` with dimensions `array`. Panics if the box is not inside `parent`.
+    pub fn new(parent: S, min: [S::Coord; N], array: [S::Coord; N]) -> Self {
+        let parent_array = parent.as_array();
+        for k in 0..N {
+            assert!(
+                S::Coord::ZERO <= min[k] && min[k] + array[k] <= parent_array[k],
+                "window axis {k} is out of bounds"
+            );
+        }
+        let size = array.iter().fold(S::Coord::ONE, |size, &dim| size * dim);
+        Self {
+            parent,
+            min,
+            array,
+            size,
+        }
+    }
+
+    #[inline]
+    pub fn parent(&self) -> &S {
+        &self.parent
+    }
+
+    /// The parent coordinates of the window's origin.
+    #[inline]
+    pub fn min(&self) -> [S::Coord; N] {
+        self.min
+    }
+
+    #[inline]
+    fn to_local(&self, mut p: [S::Coord; N]) -> [S::Coord; N] {
+        for (c, &m) in p.iter_mut().zip(self.min.iter()) {
+            *c = c.wrapping_sub(m);
+        }
+        p
+    }
+}
+
+impl<S: Shape<N>, const N: usize> Shape<N> for Window<S, N> {
+    type Coord = S::Coord;
+
+    #[inline]
+    fn size(&self) -> S::Coord {
+        self.size
+    }
+
+    #[inline]
+    fn usize(&self) -> usize {
+        self.size.to_usize()
+    }
+
+    #[inline]
+    fn as_array(&self) -> [S::Coord; N] {
+        self.array
+    }
+
+    #[inline]
+    fn strides(&self) -> [S::Coord; N] {
+        self.parent.strides()
+    }
+
+    #[inline]
+    fn linearize(&self, mut p: [S::Coord; N]) -> S::Coord {
+        for (c, &m) in p.iter_mut().zip(self.min.iter()) {
+            *c = c.wrapping_add(m);
+        }
+        self.parent.linearize(p)
+    }
+
+    #[inline]
+    fn delinearize(&self, i: S::Coord) -> [S::Coord; N] {
+        self.to_local(self.parent.delinearize(i))
+    }
+
+    /// [`Shape::delinearize`], or `None` if `i` is not the parent index of a point inside the window.
+    fn try_delinearize(&self, i: S::Coord) -> Option<[S::Coord; N]> {
+        let p = self.to_local(self.parent.try_delinearize(i)?);
+        p.iter()
+            .zip(self.array)
+            .all(|(&c, dim)| S::Coord::ZERO <= c && c < dim)
+            .then_some(p)
+    }
+
+    fn linear_range_to_boxes(
+        &self,
+        range: Range<S::Coord>,
+    ) -> Result<Vec<([S::Coord; N], [S::Coord; N])>, ShapeError> {
+        if self.min != [S::Coord::ZERO; N] || self.array != self.parent.as_array() {
+            return Err(ShapeError::IncompatibleLayout);
+        }
+        self.parent.linear_range_to_boxes(range)
+    }
+}